@@ -1,12 +1,197 @@
+mod autocomplete;
+mod autoplay;
+mod chapter;
+mod chapters;
+mod clear;
+mod common;
+mod history;
 mod join;
+mod language;
 mod leave;
 mod list;
+mod loop_mode;
+mod move_track;
+mod nowplaying;
 mod pause;
+mod picker;
 mod play;
-// mod skip;
+mod playfile;
+mod playlist;
+mod remove;
+mod replay;
+mod resume_session;
+mod seek;
+mod shuffle;
+mod skip;
+mod sponsorblock;
+mod swap;
+mod volume;
 
-pub use self::{join::join, leave::leave, list::list, pause::pause, play::play};
-// skip::skip};
+pub use self::autocomplete::SongSuggestionsKey;
+pub use self::{
+    autoplay::autoplay, chapter::chapter, chapters::chapters, clear::clear, history::history,
+    join::join, language::language, leave::leave, list::list, loop_mode::loop_mode,
+    move_track::move_track, nowplaying::nowplaying, pause::pause, play::play, playfile::playfile,
+    playlist::playlist, remove::remove, replay::replay, resume_session::resume_session, seek::seek,
+    shuffle::shuffle, skip::skip, sponsorblock::sponsorblock, swap::swap, volume::volume,
+};
+
+use std::{fmt, time::Duration};
+
+use poise::{serenity_prelude as serenity, CreateReply, FrameworkError};
+use serenity::{builder::CreateEmbed, model::Colour};
+use tracing::{error, warn};
+
+use crate::{
+    i18n::{t, Locale},
+    tools::piped::PipedError,
+};
 
 pub type Error = anyhow::Error;
 pub type Context<'a> = poise::Context<'a, (), Error>;
+
+/// The errors that are shown to the users of the commands
+#[derive(Debug)]
+pub enum BotError {
+    NotInGuild,
+    /// The user isn't in a voice channel
+    NotInVoice,
+    /// The bot isn't in a voice channel
+    NotConnected,
+    JoinFailed,
+    LeaveFailed,
+    InvalidQuery,
+    /// The song couldn't be found at the URL
+    ResolveFailed,
+    /// The attachment isn't in a supported audio format
+    NotAudioFile,
+    SearchFailed(PipedError),
+    NoResults,
+    PlaylistFailed(PipedError),
+    EmptyPlaylist,
+    EmptyQueue,
+    NothingPlaying,
+    NotEnoughTracks,
+    InvalidRange,
+    InvalidPosition(usize),
+    /// The track at the head of the queue is playing and can't be touched
+    /// without `force`
+    CurrentTrackPlaying,
+    PauseFailed,
+    ResumeFailed,
+    LoopFailed,
+    InvalidTimestamp,
+    NotSeekable,
+    SeekOutOfRange(Duration),
+    SeekFailed,
+    /// There's no saved queue to resume
+    NoSession,
+    /// The session can only be resumed into an empty queue
+    QueueNotEmpty,
+    PlaylistNotFound(String),
+    /// The guild playlist was saved by someone else
+    PlaylistNotOwned(String),
+    /// The playlist name is only whitespace
+    EmptyPlaylistName,
+    /// Nothing was played in the guild yet
+    EmptyHistory,
+    /// The current song has no chapters
+    NoChapters,
+    InvalidChapter,
+}
+
+impl BotError {
+    /// The message shown to the user
+    pub fn message(&self, locale: Locale) -> String {
+        match self {
+            BotError::NotInGuild => t!(locale, "error.not_in_guild"),
+            BotError::NotInVoice => t!(locale, "error.not_in_voice"),
+            BotError::NotConnected => t!(locale, "error.not_connected"),
+            BotError::JoinFailed => t!(locale, "error.join_failed"),
+            BotError::LeaveFailed => t!(locale, "error.leave_failed"),
+            BotError::InvalidQuery => t!(locale, "error.invalid_query"),
+            BotError::ResolveFailed => t!(locale, "error.resolve_failed"),
+            BotError::NotAudioFile => t!(locale, "error.not_audio_file"),
+            BotError::SearchFailed(_) => t!(locale, "error.search_failed"),
+            BotError::NoResults => t!(locale, "error.no_results"),
+            BotError::PlaylistFailed(_) => t!(locale, "error.playlist_failed"),
+            BotError::EmptyPlaylist => t!(locale, "error.empty_playlist"),
+            BotError::EmptyQueue => t!(locale, "error.empty_queue"),
+            BotError::NothingPlaying => t!(locale, "error.nothing_playing"),
+            BotError::NotEnoughTracks => t!(locale, "error.not_enough_tracks"),
+            BotError::InvalidRange => t!(locale, "error.invalid_range"),
+            BotError::InvalidPosition(position) => {
+                t!(locale, "error.invalid_position", position = position)
+            }
+            BotError::CurrentTrackPlaying => t!(locale, "error.current_track_playing"),
+            BotError::PauseFailed => t!(locale, "error.pause_failed"),
+            BotError::ResumeFailed => t!(locale, "error.resume_failed"),
+            BotError::LoopFailed => t!(locale, "error.loop_failed"),
+            BotError::InvalidTimestamp => t!(locale, "error.invalid_timestamp"),
+            BotError::NotSeekable => t!(locale, "error.not_seekable"),
+            BotError::SeekOutOfRange(duration) => t!(
+                locale,
+                "error.seek_out_of_range",
+                duration = common::format_timestamp(*duration)
+            ),
+            BotError::SeekFailed => t!(locale, "error.seek_failed"),
+            BotError::NoSession => t!(locale, "error.no_session"),
+            BotError::QueueNotEmpty => t!(locale, "error.queue_not_empty"),
+            BotError::PlaylistNotFound(name) => {
+                t!(locale, "error.playlist_not_found", name = name)
+            }
+            BotError::PlaylistNotOwned(name) => {
+                t!(locale, "error.playlist_not_owned", name = name)
+            }
+            BotError::EmptyPlaylistName => t!(locale, "error.empty_playlist_name"),
+            BotError::EmptyHistory => t!(locale, "error.empty_history"),
+            BotError::NoChapters => t!(locale, "error.no_chapters"),
+            BotError::InvalidChapter => t!(locale, "error.invalid_chapter"),
+        }
+    }
+}
+
+impl fmt::Display for BotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message(Locale::EnUs))
+    }
+}
+
+impl std::error::Error for BotError {}
+
+/// Shows the command errors to the user as an ephemeral embed.
+pub async fn on_error(error: FrameworkError<'_, (), Error>) {
+    let FrameworkError::Command { error, ctx, .. } = error else {
+        if let Err(cause) = poise::builtins::on_error(error).await {
+            error!(%cause, "failed to handle framework error");
+        }
+        return;
+    };
+
+    let locale = common::locale(ctx).await;
+    let command = ctx.command().qualified_name.as_str();
+    let guild_id = ctx.guild_id().map(|id| id.get());
+    let user_id = ctx.author().id.get();
+
+    let message = match error.downcast_ref::<BotError>() {
+        Some(err @ (BotError::SearchFailed(cause) | BotError::PlaylistFailed(cause))) => {
+            warn!(command, guild_id, user_id, %cause, "piped request failed");
+            err.message(locale)
+        }
+        Some(err) => {
+            warn!(command, guild_id, user_id, error = ?err, "command failed");
+            err.message(locale)
+        }
+        None => {
+            error!(command, guild_id, user_id, %error, "command failed unexpectedly");
+            t!(locale, "error.unknown")
+        }
+    };
+
+    let reply = CreateReply::default()
+        .embed(CreateEmbed::new().description(message).colour(Colour::RED))
+        .ephemeral(true);
+    if let Err(cause) = ctx.send(reply).await {
+        error!(%cause, "failed to send error message");
+    }
+}
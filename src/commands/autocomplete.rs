@@ -0,0 +1,221 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use poise::serenity_prelude as serenity;
+use serenity::{
+    all::{AutocompleteChoice, UserId},
+    prelude::TypeMapKey,
+};
+use tracing::{debug, warn};
+
+use super::{
+    common::{format_timestamp, locale, piped_instances, truncate},
+    play::search_provider,
+    Context,
+};
+use crate::{i18n::t, resolver::Resolvers, service::HttpKey, tools::piped::PipedClient};
+
+/// How long to wait for the user to stop typing before searching
+const DEBOUNCE: Duration = Duration::from_millis(300);
+/// Discord drops the suggestions that take longer than 3 seconds
+const SEARCH_TIMEOUT: Duration = Duration::from_millis(2500);
+/// Shorter queries aren't worth searching for
+const MIN_QUERY_SIZE: usize = 3;
+const MAX_SUGGESTIONS: usize = 10;
+/// How long the suggestions for a query are reused
+const CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+/// How many queries are kept in the cache
+const CACHE_SIZE: usize = 256;
+/// How long the last request of a user is remembered, which is much longer
+/// than a request can take
+const REQUEST_TTL: Duration = Duration::from_secs(60);
+/// Discord's limit for the names and values of the choices
+const CHOICE_TEXT_SIZE: usize = 100;
+
+/// The suggestions of recent queries, and the last keystroke of each user
+#[derive(Debug, Clone, Default)]
+pub struct SongSuggestions {
+    inner: Arc<Mutex<SuggestionsState>>,
+}
+
+#[derive(Debug, Default)]
+struct SuggestionsState {
+    cache: HashMap<String, CachedSuggestions>,
+    /// Counts the requests of each user, so that the older ones can tell
+    /// they were superseded
+    latest: HashMap<UserId, LatestRequest>,
+}
+
+#[derive(Debug)]
+struct LatestRequest {
+    number: u64,
+    made_at: Instant,
+}
+
+#[derive(Debug)]
+struct CachedSuggestions {
+    fetched_at: Instant,
+    /// The names and the URLs of the videos
+    choices: Vec<(String, String)>,
+}
+
+impl SongSuggestions {
+    /// Registers a new request by the user, returning its number.
+    fn begin(&self, user_id: UserId) -> u64 {
+        let mut state = self.inner.lock().expect("suggestions lock poisoned");
+        // the users who stopped typing are forgotten
+        state
+            .latest
+            .retain(|_, latest| latest.made_at.elapsed() < REQUEST_TTL);
+
+        let number = state
+            .latest
+            .get(&user_id)
+            .map_or(1, |latest| latest.number + 1);
+        state.latest.insert(
+            user_id,
+            LatestRequest {
+                number,
+                made_at: Instant::now(),
+            },
+        );
+        number
+    }
+
+    /// Whether the user made another request after `request`.
+    fn is_superseded(&self, user_id: UserId, request: u64) -> bool {
+        let state = self.inner.lock().expect("suggestions lock poisoned");
+        state.latest.get(&user_id).map(|latest| latest.number) != Some(request)
+    }
+
+    fn cached(&self, query: &str) -> Option<Vec<(String, String)>> {
+        let state = self.inner.lock().expect("suggestions lock poisoned");
+        state
+            .cache
+            .get(query)
+            .filter(|cached| cached.fetched_at.elapsed() < CACHE_TTL)
+            .map(|cached| cached.choices.clone())
+    }
+
+    fn store(&self, query: String, choices: Vec<(String, String)>) {
+        let mut state = self.inner.lock().expect("suggestions lock poisoned");
+        state
+            .cache
+            .retain(|_, cached| cached.fetched_at.elapsed() < CACHE_TTL);
+
+        if state.cache.len() >= CACHE_SIZE {
+            let oldest = state
+                .cache
+                .iter()
+                .min_by_key(|(_, cached)| cached.fetched_at)
+                .map(|(query, _)| query.clone());
+            if let Some(oldest) = oldest {
+                state.cache.remove(&oldest);
+            }
+        }
+
+        state.cache.insert(
+            query,
+            CachedSuggestions {
+                fetched_at: Instant::now(),
+                choices,
+            },
+        );
+    }
+}
+
+pub struct SongSuggestionsKey;
+
+impl TypeMapKey for SongSuggestionsKey {
+    type Value = SongSuggestions;
+}
+
+/// Suggests videos for the `song` of `/play` as the user types. Picking one
+/// makes the command receive the video's URL.
+pub(super) async fn autocomplete_song(ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice> {
+    // the other providers' searches can't be suggested
+    if search_provider(&Resolvers::default(), partial).is_some() {
+        return Vec::new();
+    }
+
+    let query = partial.trim();
+    let query = query
+        .strip_prefix("yt:")
+        .unwrap_or(query)
+        .trim()
+        .to_lowercase();
+    if query.chars().count() < MIN_QUERY_SIZE || query.starts_with("http") {
+        return Vec::new();
+    }
+
+    // the names of the choices are translated
+    let locale = locale(ctx).await;
+    let cache_key = format!("{locale:?}:{query}");
+
+    let (suggestions, http) = {
+        let data = ctx.serenity_context().data.read().await;
+        (
+            data.get::<SongSuggestionsKey>()
+                .expect("song suggestions not found")
+                .clone(),
+            data.get::<HttpKey>()
+                .expect("http client not found")
+                .clone(),
+        )
+    };
+
+    let choices = match suggestions.cached(&cache_key) {
+        Some(choices) => choices,
+        None => {
+            let user_id = ctx.author().id;
+            let request = suggestions.begin(user_id);
+            tokio::time::sleep(DEBOUNCE).await;
+            if suggestions.is_superseded(user_id, request) {
+                debug!(query, "skipping superseded suggestions");
+                return Vec::new();
+            }
+
+            let instances = piped_instances(ctx).await;
+            let client = PipedClient::new(&http, &instances);
+            let search = client.search_songs(&query);
+            let results = match tokio::time::timeout(SEARCH_TIMEOUT, search).await {
+                Ok(Ok(results)) => results,
+                Ok(Err(cause)) => {
+                    warn!(%cause, "failed to search for suggestions");
+                    return Vec::new();
+                }
+                Err(_) => {
+                    warn!(query, "the search for suggestions timed out");
+                    return Vec::new();
+                }
+            };
+
+            let choices = results
+                .into_streams()
+                .take(MAX_SUGGESTIONS)
+                .map(|result| {
+                    let duration = match result.duration() {
+                        Some(duration) => format_timestamp(duration),
+                        None => t!(locale, "player.live"),
+                    };
+                    (
+                        truncate(&format!("{} ({duration})", result.title), CHOICE_TEXT_SIZE),
+                        format!("https://www.youtube.com{}", result.url),
+                    )
+                })
+                .filter(|(_, url)| url.len() <= CHOICE_TEXT_SIZE)
+                .collect::<Vec<_>>();
+
+            suggestions.store(cache_key, choices.clone());
+            choices
+        }
+    };
+
+    choices
+        .into_iter()
+        .map(|(name, url)| AutocompleteChoice::new(name, url))
+        .collect()
+}
@@ -0,0 +1,47 @@
+use super::{
+    common::{guild_settings, locale},
+    BotError, Context, Error,
+};
+use crate::i18n::t;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum AutoplayMode {
+    #[name = "on"]
+    #[name_localized("pt-BR", "ligado")]
+    On,
+    #[name = "off"]
+    #[name_localized("pt-BR", "desligado")]
+    Off,
+}
+
+/// Plays related songs when the queue runs out
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("pt-BR", "Toca músicas relacionadas quando a fila acaba")
+)]
+pub async fn autoplay(
+    ctx: Context<'_>,
+    #[description = "Whether related songs should be played"]
+    #[description_localized("pt-BR", "Se músicas relacionadas devem ser tocadas")]
+    #[name_localized("pt-BR", "modo")]
+    mode: AutoplayMode,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    let enabled = mode == AutoplayMode::On;
+
+    guild_settings(ctx)
+        .await
+        .update(guild_id, |settings| settings.autoplay = enabled)
+        .await;
+
+    let locale = locale(ctx).await;
+    let message = if enabled {
+        t!(locale, "autoplay.on")
+    } else {
+        t!(locale, "autoplay.off")
+    };
+    ctx.reply(message).await?;
+
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use std::{sync::Arc, time::Duration};
+
+use serenity::all::GuildId;
+use songbird::{tracks::TrackHandle, Songbird};
+use tracing::error;
+
+use super::{
+    common::{format_timestamp, locale},
+    BotError, Context, Error,
+};
+use crate::{
+    events::chapters::ChapterJumpKey,
+    i18n::t,
+    player::{SongMetadata, SongMetadataKey},
+};
+
+/// Jumps to a chapter of the current song
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("pt-BR", "Pula para um capítulo da música atual")
+)]
+pub async fn chapter(
+    ctx: Context<'_>,
+    #[description = "next, prev or the number of the chapter"]
+    #[description_localized("pt-BR", "próximo, anterior ou o número do capítulo")]
+    #[name_localized("pt-BR", "capítulo")]
+    target: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird Voice client placed in at initialisation.");
+
+    let target = ChapterTarget::parse(&target).ok_or(BotError::InvalidChapter)?;
+
+    ctx.defer().await?;
+
+    let (current, meta, position) = current_chapters(manager, guild_id).await?;
+    let playing = meta.chapter_at(position).unwrap_or(0);
+    let chapters = meta.chapters.unwrap_or_default();
+    let index = match target {
+        ChapterTarget::Next => playing + 1,
+        ChapterTarget::Previous => playing.saturating_sub(1),
+        ChapterTarget::Number(number) => number.checked_sub(1).ok_or(BotError::InvalidChapter)?,
+    };
+    let chapter = chapters.get(index).ok_or(BotError::InvalidChapter)?;
+
+    current.seek_async(chapter.start).await.map_err(|cause| {
+        error!(%cause, "failed to seek");
+        BotError::SeekFailed
+    })?;
+    // jumping to the start of the chapter that's playing isn't a change the
+    // notifier would see, so the key would linger
+    if index != playing {
+        current
+            .typemap()
+            .write()
+            .await
+            .insert::<ChapterJumpKey>(index);
+    }
+
+    ctx.reply(t!(
+        locale(ctx).await,
+        "chapter.seeking",
+        number = index + 1,
+        title = chapter.title,
+        position = format_timestamp(chapter.start)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Which chapter to jump to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChapterTarget {
+    Next,
+    Previous,
+    /// Counting from 1, as in `/chapters`
+    Number(usize),
+}
+
+impl ChapterTarget {
+    fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_lowercase().as_str() {
+            "next" | "próximo" | "proximo" => Some(Self::Next),
+            "prev" | "previous" | "anterior" => Some(Self::Previous),
+            number => number.parse().ok().map(Self::Number),
+        }
+    }
+}
+
+/// The current track, its metadata and its position, if it has chapters.
+pub(super) async fn current_chapters(
+    manager: Arc<Songbird>,
+    guild_id: GuildId,
+) -> Result<(TrackHandle, SongMetadata, Duration), BotError> {
+    let Some(handler_lock) = manager.get(guild_id) else {
+        return Err(BotError::NotConnected);
+    };
+    let Some(current) = handler_lock.lock().await.queue().current() else {
+        return Err(BotError::NothingPlaying);
+    };
+
+    let meta = {
+        let map = current.typemap().read().await;
+        map.get::<SongMetadataKey>().cloned()
+    };
+    let Some(meta) = meta.filter(|meta| {
+        meta.chapters
+            .as_ref()
+            .is_some_and(|chapters| !chapters.is_empty())
+    }) else {
+        return Err(BotError::NoChapters);
+    };
+
+    let position = current
+        .get_info()
+        .await
+        .map(|info| info.position)
+        .unwrap_or_default();
+
+    Ok((current, meta, position))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_directions() {
+        assert_eq!(ChapterTarget::parse("next"), Some(ChapterTarget::Next));
+        assert_eq!(ChapterTarget::parse(" Próximo "), Some(ChapterTarget::Next));
+        assert_eq!(ChapterTarget::parse("proximo"), Some(ChapterTarget::Next));
+        assert_eq!(ChapterTarget::parse("prev"), Some(ChapterTarget::Previous));
+        assert_eq!(
+            ChapterTarget::parse("PREVIOUS"),
+            Some(ChapterTarget::Previous)
+        );
+        assert_eq!(
+            ChapterTarget::parse("anterior"),
+            Some(ChapterTarget::Previous)
+        );
+    }
+
+    #[test]
+    fn parses_numbers() {
+        assert_eq!(ChapterTarget::parse("3"), Some(ChapterTarget::Number(3)));
+        assert_eq!(
+            ChapterTarget::parse(" 12 "),
+            Some(ChapterTarget::Number(12))
+        );
+        // rejected once the chapters are known, since they count from 1
+        assert_eq!(ChapterTarget::parse("0"), Some(ChapterTarget::Number(0)));
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        for input in ["", "nxt", "-1", "1.5", "first"] {
+            assert_eq!(ChapterTarget::parse(input), None, "{input:?}");
+        }
+    }
+}
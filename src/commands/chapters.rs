@@ -0,0 +1,81 @@
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::{builder::CreateEmbed, model::Colour};
+
+use super::{
+    chapter::current_chapters,
+    common::{format_timestamp, locale, truncate},
+    BotError, Context, Error,
+};
+use crate::i18n::t;
+
+/// How many chapters are shown in each page
+const CHAPTER_PAGE_SIZE: usize = 15;
+const MAX_TITLE_LENGTH: usize = 60;
+
+/// Lists the chapters of the current song
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("pt-BR", "Lista os capítulos da música atual")
+)]
+pub async fn chapters(
+    ctx: Context<'_>,
+    #[description = "The page of the list, the one with the current chapter by default"]
+    #[description_localized("pt-BR", "Escolhe a página da lista, a do capítulo atual por padrão")]
+    #[name_localized("pt-BR", "página")]
+    #[min = 1]
+    page: Option<usize>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird Voice client placed in at initialisation.");
+
+    let (_, meta, position) = current_chapters(manager, guild_id).await?;
+    let playing = meta.chapter_at(position);
+    let chapters = meta.chapters.unwrap_or_default();
+
+    let pages = chapters.len().div_ceil(CHAPTER_PAGE_SIZE);
+    let page = page
+        .unwrap_or_else(|| playing.unwrap_or(0) / CHAPTER_PAGE_SIZE + 1)
+        .clamp(1, pages);
+    let start_at = (page - 1) * CHAPTER_PAGE_SIZE;
+
+    let lines = chapters
+        .iter()
+        .enumerate()
+        .skip(start_at)
+        .take(CHAPTER_PAGE_SIZE)
+        .map(|(i, chapter)| {
+            let line = format!(
+                "{}. `{}` {}",
+                i + 1,
+                format_timestamp(chapter.start),
+                truncate(&chapter.title, MAX_TITLE_LENGTH)
+            );
+            if playing == Some(i) {
+                format!("**{line}** ◀")
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let header = t!(
+        locale(ctx).await,
+        "chapters.header",
+        title = truncate(&meta.title, MAX_TITLE_LENGTH),
+        page = page,
+        pages = pages
+    );
+    let response = CreateReply::default()
+        .embed(
+            CreateEmbed::new()
+                .field(header, lines.join("\n"), false)
+                .colour(Colour::BLUE),
+        )
+        .ephemeral(true);
+    ctx.send(response).await?;
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use serenity::all::GuildId;
+use songbird::Songbird;
+
+use super::{
+    common::{format_titles, locale, track_titles},
+    BotError, Context, Error,
+};
+use crate::i18n::t;
+
+/// Clears the queue, keeping the current song
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("pt-BR", "Limpa a fila de reprodução, mantendo a música atual")
+)]
+pub async fn clear(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird Voice client placed in at initialisation.");
+
+    let titles = clear_queue(manager, guild_id).await?;
+    let locale = locale(ctx).await;
+    ctx.reply(t!(
+        locale,
+        "clear.removing",
+        count = titles.len(),
+        titles = format_titles(locale, &titles)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+pub(super) async fn clear_queue(
+    manager: Arc<Songbird>,
+    guild_id: GuildId,
+) -> Result<Vec<String>, BotError> {
+    let Some(handler_lock) = manager.get(guild_id) else {
+        return Err(BotError::NotConnected);
+    };
+    let handler = handler_lock.lock().await;
+
+    let removed = handler.queue().modify_queue(|q| {
+        if q.len() <= 1 {
+            return Err(BotError::EmptyQueue);
+        }
+
+        let removed = q.drain(1..).collect::<Vec<_>>();
+        for track in &removed {
+            track.stop().ok();
+        }
+
+        Ok(removed)
+    })?;
+
+    Ok(track_titles(removed).await)
+}
@@ -0,0 +1,281 @@
+use std::{collections::VecDeque, sync::Arc, time::Duration};
+
+use reqwest::Client as HttpClient;
+use songbird::{
+    tracks::{PlayMode, Queued, TrackHandle, TrackQueue},
+    Call,
+};
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use super::{BotError, Context};
+use crate::{
+    config::{Config, ConfigKey},
+    guild::{GuildSettingsKey, GuildSettingsStore},
+    history::{HistoryKey, HistoryStore},
+    i18n::{t, Locale},
+    player::{Player, SongMetadata, SongMetadataKey},
+    playlists::{PlaylistStore, PlaylistStoreKey},
+    resolver::ResolveContext,
+    service::{HttpKey, PipedKey},
+    session::{SessionStore, SessionStoreKey},
+    tools::piped::PipedInstances,
+};
+
+const MAX_LISTED_TITLES: usize = 10;
+
+/// Creates a [`Player`] for the guild's `call` that notifies the channel the
+/// command was used in.
+pub(super) async fn player(ctx: Context<'_>, call: Arc<Mutex<Call>>) -> Player {
+    let guild_id = ctx.guild_id().expect("guild id not found");
+    let locale = locale(ctx).await;
+    let data = ctx.serenity_context().data.read().await;
+
+    Player {
+        guild_id,
+        call,
+        channel_id: ctx.channel_id(),
+        http: ctx.serenity_context().http.clone(),
+        client: data
+            .get::<HttpKey>()
+            .expect("http client not found")
+            .clone(),
+        settings: data
+            .get::<GuildSettingsKey>()
+            .expect("guild settings not found")
+            .clone(),
+        locale,
+        history: data.get::<HistoryKey>().expect("history not found").clone(),
+        piped: data
+            .get::<PipedKey>()
+            .expect("piped instances not found")
+            .clone(),
+    }
+}
+
+pub(super) async fn http_client(ctx: Context<'_>) -> HttpClient {
+    let data = ctx.serenity_context().data.read().await;
+    data.get::<HttpKey>()
+        .expect("http client not found")
+        .clone()
+}
+
+/// What the resolvers need to find songs for the author of the command.
+pub(super) async fn resolve_context(ctx: Context<'_>) -> ResolveContext {
+    let data = ctx.serenity_context().data.read().await;
+    ResolveContext {
+        http: data
+            .get::<HttpKey>()
+            .expect("http client not found")
+            .clone(),
+        piped: data
+            .get::<PipedKey>()
+            .expect("piped instances not found")
+            .clone(),
+        max_playlist_size: data
+            .get::<ConfigKey>()
+            .expect("config not found")
+            .max_playlist_size,
+        requester: requester(ctx),
+    }
+}
+
+pub(super) async fn guild_settings(ctx: Context<'_>) -> GuildSettingsStore {
+    let data = ctx.serenity_context().data.read().await;
+    data.get::<GuildSettingsKey>()
+        .expect("guild settings not found")
+        .clone()
+}
+
+pub(super) async fn config(ctx: Context<'_>) -> Config {
+    let data = ctx.serenity_context().data.read().await;
+    data.get::<ConfigKey>().expect("config not found").clone()
+}
+
+pub(super) async fn session_store(ctx: Context<'_>) -> SessionStore {
+    let data = ctx.serenity_context().data.read().await;
+    data.get::<SessionStoreKey>()
+        .expect("session store not found")
+        .clone()
+}
+
+pub(super) async fn playlist_store(ctx: Context<'_>) -> PlaylistStore {
+    let data = ctx.serenity_context().data.read().await;
+    data.get::<PlaylistStoreKey>()
+        .expect("playlist store not found")
+        .clone()
+}
+
+pub(super) async fn played_history(ctx: Context<'_>) -> HistoryStore {
+    let data = ctx.serenity_context().data.read().await;
+    data.get::<HistoryKey>().expect("history not found").clone()
+}
+
+pub(super) async fn piped_instances(ctx: Context<'_>) -> PipedInstances {
+    let data = ctx.serenity_context().data.read().await;
+    data.get::<PipedKey>()
+        .expect("piped instances not found")
+        .clone()
+}
+
+/// The language used to answer the command: the one chosen for the guild,
+/// or else the user's.
+pub(super) async fn locale(ctx: Context<'_>) -> Locale {
+    if let Some(guild_id) = ctx.guild_id() {
+        if let Some(locale) = guild_settings(ctx).await.get(guild_id).await.locale {
+            return locale;
+        }
+    }
+
+    ctx.locale().map(Locale::from_code).unwrap_or_default()
+}
+
+/// Cuts `text` to at most `size` characters, ending it with an ellipsis if
+/// anything was cut.
+pub(super) fn truncate(text: &str, size: usize) -> String {
+    match text.char_indices().nth(size - 1) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+/// The name shown for the author of the command.
+pub(super) fn requester(ctx: Context<'_>) -> String {
+    ctx.author()
+        .global_name
+        .clone()
+        .unwrap_or(ctx.author().name.clone())
+}
+
+/// Reads the title stored in the track's [`SongMetadataKey`].
+pub(super) async fn track_title(track: &TrackHandle) -> Option<String> {
+    let map = track.typemap().read().await;
+    map.get::<SongMetadataKey>().map(|meta| meta.title.clone())
+}
+
+/// Reads the [`SongMetadataKey`] of all `tracks`, skipping the ones without
+/// it.
+pub(super) async fn track_songs<I>(tracks: I) -> Vec<SongMetadata>
+where
+    I: IntoIterator,
+    I::Item: std::ops::Deref<Target = TrackHandle>,
+{
+    let mut songs = Vec::new();
+    for track in tracks {
+        let map = track.typemap().read().await;
+        if let Some(meta) = map.get::<SongMetadataKey>() {
+            songs.push(meta.clone());
+        }
+    }
+    songs
+}
+
+/// Reads the titles of all `tracks`, skipping the ones without metadata.
+pub(super) async fn track_titles<I>(tracks: I) -> Vec<String>
+where
+    I: IntoIterator,
+    I::Item: std::ops::Deref<Target = TrackHandle>,
+{
+    let mut titles = Vec::new();
+    for track in tracks {
+        if let Some(title) = track_title(&track).await {
+            titles.push(title);
+        }
+    }
+    titles
+}
+
+/// Formats `titles` as a markdown list, truncated to a few entries so the
+/// message stays within Discord's size limits.
+pub(super) fn format_titles(locale: Locale, titles: &[String]) -> String {
+    let mut list = titles
+        .iter()
+        .take(MAX_LISTED_TITLES)
+        .map(|title| format!("- {title}"))
+        .collect::<Vec<_>>();
+
+    if titles.len() > MAX_LISTED_TITLES {
+        let count = titles.len() - MAX_LISTED_TITLES;
+        list.push(format!("- {}", t!(locale, "queue.more", count = count)));
+    }
+
+    list.join("\n")
+}
+
+/// Formats a duration as `m:ss`, or `h:mm:ss` when it is longer than an hour.
+pub(super) fn format_timestamp(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+/// Whether the track is currently being played (i.e. not paused or stopped).
+pub(super) async fn is_playing(track: &TrackHandle) -> bool {
+    track
+        .get_info()
+        .await
+        .is_ok_and(|info| info.playing == PlayMode::Play)
+}
+
+/// Starts the first playable track in the queue, discarding the ones that
+/// fail to play.
+///
+/// Songbird only advances the queue when its head ends by itself, so this
+/// must be called whenever the head is removed or replaced by hand.
+pub(super) fn play_head(q: &mut VecDeque<Queued>) {
+    while let Some(track) = q.front() {
+        if track.play().is_ok() {
+            break;
+        }
+        warn!("failed to play the next track, discarding it");
+        if let Some(track) = q.pop_front() {
+            track.stop().ok();
+        }
+    }
+}
+
+/// Makes sure the head of the queue is playing after it was reordered,
+/// pausing `previous_head` if it got moved away from the front.
+pub(super) fn sync_head(q: &mut VecDeque<Queued>, previous_head: Option<TrackHandle>) {
+    let Some(previous_head) = previous_head else {
+        return;
+    };
+    if q.front()
+        .is_some_and(|head| head.uuid() == previous_head.uuid())
+    {
+        return;
+    }
+
+    previous_head.pause().ok();
+    play_head(q);
+}
+
+/// Refuses to touch the track that is currently playing unless `force` is
+/// set.
+pub(super) async fn guard_current(
+    queue: &TrackQueue,
+    touches_current: bool,
+    force: bool,
+) -> Result<(), BotError> {
+    if !touches_current || force {
+        return Ok(());
+    }
+
+    match queue.current() {
+        Some(current) if is_playing(&current).await => Err(BotError::CurrentTrackPlaying),
+        _ => Ok(()),
+    }
+}
+
+/// Converts a 1-based queue position into an index of a queue of size `len`.
+pub(super) fn queue_index(position: usize, len: usize) -> Result<usize, BotError> {
+    if position == 0 || position > len {
+        return Err(BotError::InvalidPosition(position));
+    }
+    Ok(position - 1)
+}
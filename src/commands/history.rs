@@ -0,0 +1,73 @@
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::{builder::CreateEmbed, model::Colour};
+
+use super::{
+    common::{locale, played_history, truncate},
+    BotError, Context, Error,
+};
+use crate::i18n::t;
+
+/// How many entries are shown in each page
+const HISTORY_PAGE_SIZE: usize = 10;
+const MAX_TITLE_LENGTH: usize = 60;
+
+/// Lists the songs that were played, most recent first
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized(
+        "pt-BR",
+        "Lista as músicas que já tocaram, das mais recentes às mais antigas"
+    )
+)]
+pub async fn history(
+    ctx: Context<'_>,
+    #[description = "The page of the list"]
+    #[description_localized("pt-BR", "Escolhe a página da lista")]
+    #[name_localized("pt-BR", "página")]
+    #[min = 1]
+    page: Option<usize>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    let entries = played_history(ctx).await.get(guild_id).await;
+    if entries.is_empty() {
+        return Err(BotError::EmptyHistory.into());
+    }
+
+    let locale = locale(ctx).await;
+    let pages = entries.len().div_ceil(HISTORY_PAGE_SIZE);
+    let page = page.unwrap_or(1).clamp(1, pages);
+    let start_at = (page - 1) * HISTORY_PAGE_SIZE;
+
+    let lines = entries
+        .iter()
+        .enumerate()
+        .skip(start_at)
+        .take(HISTORY_PAGE_SIZE)
+        .map(|(i, entry)| {
+            let mut line = format!(
+                "{}. {} · {} · <t:{}:R>",
+                i + 1,
+                truncate(&entry.song.title, MAX_TITLE_LENGTH),
+                entry.song.user,
+                entry.started_at.unix_timestamp()
+            );
+            if entry.skipped {
+                line.push_str(&format!(" ({})", t!(locale, "history.skipped")));
+            }
+            line
+        })
+        .collect::<Vec<_>>();
+
+    let header = t!(locale, "history.header", page = page, pages = pages);
+    let response = CreateReply::default()
+        .embed(
+            CreateEmbed::new()
+                .field(header, lines.join("\n"), false)
+                .colour(Colour::BLUE),
+        )
+        .ephemeral(true);
+    ctx.send(response).await?;
+
+    Ok(())
+}
@@ -1,43 +1,49 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
-use serenity::all::ChannelId;
+use serenity::all::{ChannelId, GuildId};
 use serenity::model::guild::Guild;
-use songbird::{Call, Songbird};
+use songbird::{Call, Event, Songbird};
 use tokio::sync::Mutex;
 use tracing::{error, info};
 
-use super::{Context, Error};
+use super::{
+    common::{config, guild_settings, locale},
+    BotError, Context, Error,
+};
+use crate::{events::voice::IdleLeaver, i18n::t};
 
-/// Entra no canal de voz que você está
-#[poise::command(slash_command, guild_only)]
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Joins the voice channel you are in
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("pt-BR", "Entra no canal de voz que você está")
+)]
 pub async fn join(ctx: Context<'_>) -> Result<(), Error> {
-    let guild = ctx
-        .guild()
-        .ok_or(anyhow::anyhow!("Not in a guild"))?
-        .clone();
+    let guild = ctx.guild().ok_or(BotError::NotInGuild)?.clone();
 
     let manager = songbird::get(ctx.serenity_context())
         .await
         .ok_or(anyhow::anyhow!("Songbird Voice client not found"))?;
 
-    match join_channel(manager, &ctx, &guild).await {
-        Ok((_, channel_id)) => {
-            let channel_name = channel_id
-                .name(&ctx.http())
-                .await
-                .unwrap_or(String::from("?"));
+    let (_, channel_id) = join_channel(manager, &ctx, &guild).await?;
+    let channel_name = channel_id
+        .name(&ctx.http())
+        .await
+        .unwrap_or(String::from("?"));
 
-            info!(
-                channel_id = channel_id.get(),
-                ?channel_name,
-                "joining channel"
-            );
-            ctx.reply(format!("Entrando em **{channel_name}**")).await?;
-        }
-        Err(e) => {
-            ctx.reply(e.to_string()).await?;
-        }
-    };
+    info!(
+        channel_id = channel_id.get(),
+        ?channel_name,
+        "joining channel"
+    );
+    ctx.reply(t!(
+        locale(ctx).await,
+        "join.joining",
+        channel = channel_name
+    ))
+    .await?;
 
     Ok(())
 }
@@ -46,7 +52,7 @@ pub(super) async fn join_channel(
     manager: Arc<Songbird>,
     ctx: &Context<'_>,
     guild: &Guild,
-) -> Result<(Arc<Mutex<Call>>, ChannelId), Error> {
+) -> Result<(Arc<Mutex<Call>>, ChannelId), BotError> {
     let user_voice_channel = guild
         .voice_states
         .get(&ctx.author().id)
@@ -55,21 +61,49 @@ pub(super) async fn join_channel(
     let connect_to = match user_voice_channel {
         Some(channel) => channel,
         None => {
-            // check_msg(cmd.reply(ctx, "Not in a voice channel").await);
             error!("not in a voice channel");
-            return Err(anyhow::anyhow!(
-                "Você deve entrar em um canal de voz antes de usar esse comando"
-            ));
+            return Err(BotError::NotInVoice);
         }
     };
 
-    manager.join(guild.id, connect_to).await.map_or_else(
-        |cause| {
+    let handler = connect_channel(manager, ctx, guild.id, connect_to).await?;
+    Ok((handler, connect_to))
+}
+
+/// Joins `channel_id`, setting up the call if the bot wasn't in any channel
+/// of the guild.
+pub(super) async fn connect_channel(
+    manager: Arc<Songbird>,
+    ctx: &Context<'_>,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+) -> Result<Arc<Mutex<Call>>, BotError> {
+    let already_joined = manager.get(guild_id).is_some();
+
+    let handler = match manager.join(guild_id, channel_id).await {
+        Ok(handler) => handler,
+        Err(cause) => {
             error!(%cause, "failed to join channel");
-            Err(anyhow::anyhow!(
-                "Não consegui entrar no canal. Tente novamente mais tarde".to_string()
-            ))
-        },
-        |handler| Ok((handler, connect_to)),
-    )
+            return Err(BotError::JoinFailed);
+        }
+    };
+
+    // the call keeps its global events until the bot leaves it
+    if !already_joined {
+        let timeout = config(*ctx).await.idle_timeout;
+        handler.lock().await.add_global_event(
+            Event::Periodic(IDLE_CHECK_INTERVAL, None),
+            IdleLeaver {
+                guild_id,
+                manager,
+                cache: ctx.serenity_context().cache.clone(),
+                http: ctx.serenity_context().http.clone(),
+                settings: guild_settings(*ctx).await,
+                timeout,
+                idle_since: Default::default(),
+            },
+        );
+    }
+
+    Ok(handler)
 }
@@ -0,0 +1,40 @@
+use poise::ChoiceParameter;
+
+use super::{
+    common::{guild_settings, locale},
+    BotError, Context, Error,
+};
+use crate::i18n::{t, Locale};
+
+/// Changes the language the bot speaks in this server
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("pt-BR", "Muda o idioma que o bot fala neste servidor")
+)]
+pub async fn language(
+    ctx: Context<'_>,
+    #[description = "The language to use. Leave empty to use each person's language"]
+    #[description_localized(
+        "pt-BR",
+        "O idioma a ser usado. Deixe vazio para usar o idioma de cada pessoa"
+    )]
+    #[name_localized("pt-BR", "idioma")]
+    language: Option<Locale>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+
+    guild_settings(ctx)
+        .await
+        .update(guild_id, |settings| settings.locale = language)
+        .await;
+
+    let locale = locale(ctx).await;
+    let message = match language {
+        Some(language) => t!(locale, "language.set", language = language.name()),
+        None => t!(locale, "language.auto"),
+    };
+    ctx.reply(message).await?;
+
+    Ok(())
+}
@@ -1,30 +1,31 @@
 use tracing::error;
 
-use super::{Context, Error};
+use super::{common::locale, BotError, Context, Error};
+use crate::i18n::t;
 
-/// Leave the voice channel
-
-/// Sai do canal de voz
-#[poise::command(slash_command, guild_only)]
+/// Leaves the voice channel
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("pt-BR", "Sai do canal de voz")
+)]
 pub async fn leave(ctx: Context<'_>) -> Result<(), Error> {
-    let guild_id = ctx.guild_id().ok_or(anyhow::anyhow!("Not in a guild"))?;
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
 
-    let manager = songbird::get(&ctx.serenity_context())
+    let manager = songbird::get(ctx.serenity_context())
         .await
         .expect("Songbird Voice client placed in at initialisation.")
         .clone();
 
     if manager.get(guild_id).is_none() {
-        ctx.reply("Não estou em nenhum canal").await?;
-        return Err(anyhow::anyhow!("not in a channel"));
+        return Err(BotError::NotConnected.into());
     }
 
     if let Err(cause) = manager.remove(guild_id).await {
         error!(%cause, "failed to leave channel");
-        ctx.reply("Não consegui sair do canal").await?;
-    } else {
-        ctx.reply("Saindo do canal").await?;
+        return Err(BotError::LeaveFailed.into());
     }
+    ctx.reply(t!(locale(ctx).await, "leave.leaving")).await?;
 
     Ok(())
 }
@@ -4,52 +4,66 @@ use poise::{serenity_prelude as serenity, CreateReply};
 use serenity::{all::GuildId, builder::CreateEmbed, model::Colour};
 use songbird::Songbird;
 
-use super::{play::SongMetadataKey, Context, Error};
+use super::{
+    common::{guild_settings, locale},
+    BotError, Context, Error,
+};
+use crate::{
+    i18n::{t, Locale},
+    player::SongMetadataKey,
+};
 
 const TRACK_LIST_SIZE: usize = 10;
 
-/// Lista as músicas na fila de reprodução
-#[poise::command(slash_command, guild_only)]
+/// Lists the songs in the queue
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("pt-BR", "Lista as músicas na fila de reprodução")
+)]
 pub async fn list(
     ctx: Context<'_>,
-    #[description = "Escolhe a página da lista"] option_page: Option<String>,
+    #[description = "The page of the list"]
+    #[description_localized("pt-BR", "Escolhe a página da lista")]
+    option_page: Option<String>,
 ) -> Result<(), Error> {
     let option_page = option_page
         .and_then(|page| page.parse::<usize>().ok())
         .unwrap_or(1);
 
-    let guild_id = ctx.guild_id().ok_or(anyhow::anyhow!("Not in a guild"))?;
-    let manager = songbird::get(&ctx.serenity_context())
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    let manager = songbird::get(ctx.serenity_context())
         .await
         .expect("Songbird Voice client placed in at initialisation.");
 
-    match show_list(manager, guild_id, option_page - 1).await {
-        Ok(message) => {
-            let response = CreateReply::default()
-                .embed(
-                    CreateEmbed::new()
-                        .field("LISTA DE REPRODUÇÃO ATUAL", message, false)
-                        .colour(Colour::BLUE),
-                )
-                .ephemeral(true);
-            let _ = ctx.send(response).await;
-
-            Ok(())
-        }
-        Err(err) => {
-            ctx.reply(err.to_string()).await?;
-            Ok(())
-        }
-    }
+    let locale = locale(ctx).await;
+    let settings = guild_settings(ctx).await.get(guild_id).await;
+    let header = match settings.loop_mode.label(locale) {
+        Some(label) => format!("{} ({label})", t!(locale, "queue.header")),
+        None => t!(locale, "queue.header"),
+    };
+
+    let message = show_list(manager, guild_id, locale, option_page.saturating_sub(1)).await?;
+    let response = CreateReply::default()
+        .embed(
+            CreateEmbed::new()
+                .field(header, message, false)
+                .colour(Colour::BLUE),
+        )
+        .ephemeral(true);
+    let _ = ctx.send(response).await;
+
+    Ok(())
 }
 
 pub(super) async fn show_list(
     manager: Arc<Songbird>,
     guild_id: GuildId,
+    locale: Locale,
     page: usize,
-) -> Result<String, String> {
+) -> Result<String, BotError> {
     let Some(handler_lock) = manager.get(guild_id) else {
-        return Err("Não estou em nenhum canal".to_string());
+        return Err(BotError::NotConnected);
     };
 
     let handler = handler_lock.lock().await;
@@ -60,7 +74,7 @@ pub(super) async fn show_list(
     let list_size = q.len().min(TRACK_LIST_SIZE);
 
     if list_size == 0 {
-        return Err("A lista está vazia".into());
+        return Err(BotError::EmptyQueue);
     }
 
     let mut list = Vec::with_capacity(list_size);
@@ -79,7 +93,12 @@ pub(super) async fn show_list(
 
         let position = i + start_at + 1;
 
-        list.push(format!("{position}. {}", meta.title));
+        if meta.autoplayed {
+            let label = t!(locale, "autoplay.label");
+            list.push(format!("{position}. {} *({label})*", meta.title));
+        } else {
+            list.push(format!("{position}. {}", meta.title));
+        }
     }
 
     Ok(list.join("\n"))
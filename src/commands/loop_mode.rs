@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use serenity::all::GuildId;
+use songbird::Songbird;
+
+use super::{
+    common::{guild_settings, locale},
+    BotError, Context, Error,
+};
+use crate::{
+    guild::{GuildSettingsStore, LoopMode},
+    i18n::t,
+};
+
+/// Loops the current song or the whole queue
+#[poise::command(
+    slash_command,
+    guild_only,
+    rename = "loop",
+    description_localized("pt-BR", "Repete a música atual ou a fila inteira")
+)]
+pub async fn loop_mode(
+    ctx: Context<'_>,
+    #[description = "What should be looped"]
+    #[description_localized("pt-BR", "O que deve ser repetido")]
+    #[name_localized("pt-BR", "modo")]
+    mode: LoopMode,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird Voice client placed in at initialisation.");
+    let settings = guild_settings(ctx).await;
+
+    set_loop_mode(manager, settings, guild_id, mode).await?;
+    let locale = locale(ctx).await;
+    let message = mode.label(locale).unwrap_or_else(|| t!(locale, "loop.off"));
+    ctx.reply(message).await?;
+
+    Ok(())
+}
+
+pub(super) async fn set_loop_mode(
+    manager: Arc<Songbird>,
+    settings: GuildSettingsStore,
+    guild_id: GuildId,
+    mode: LoopMode,
+) -> Result<(), BotError> {
+    settings
+        .update(guild_id, |settings| settings.loop_mode = mode)
+        .await;
+
+    // the mode is applied to the other tracks as they start playing
+    let Some(handler_lock) = manager.get(guild_id) else {
+        return Ok(());
+    };
+    let handler = handler_lock.lock().await;
+    let Some(current) = handler.queue().current() else {
+        return Ok(());
+    };
+
+    let result = if mode == LoopMode::Track {
+        current.enable_loop()
+    } else {
+        current.disable_loop()
+    };
+    result.map_err(|_| BotError::LoopFailed)
+}
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use serenity::all::GuildId;
+use songbird::Songbird;
+
+use super::{
+    common::{guard_current, locale, queue_index, sync_head, track_title},
+    BotError, Context, Error,
+};
+use crate::i18n::t;
+
+/// Moves a song to another position of the queue
+#[poise::command(
+    slash_command,
+    guild_only,
+    rename = "move",
+    description_localized("pt-BR", "Move uma música para outra posição da fila")
+)]
+pub async fn move_track(
+    ctx: Context<'_>,
+    #[description = "The current position of the song"]
+    #[description_localized("pt-BR", "A posição atual da música")]
+    #[name_localized("pt-BR", "de")]
+    #[min = 1]
+    from: usize,
+    #[description = "The new position of the song"]
+    #[description_localized("pt-BR", "A nova posição da música")]
+    #[name_localized("pt-BR", "para")]
+    #[min = 1]
+    to: usize,
+    #[description = "Allows moving the song that is playing"]
+    #[description_localized("pt-BR", "Permite mover a música que está tocando")]
+    #[name_localized("pt-BR", "forçar")]
+    force: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird Voice client placed in at initialisation.");
+
+    let title = move_in_queue(manager, guild_id, (from, to), force.unwrap_or(false)).await?;
+    ctx.reply(t!(
+        locale(ctx).await,
+        "move.moving",
+        title = title,
+        position = to
+    ))
+    .await?;
+
+    Ok(())
+}
+
+pub(super) async fn move_in_queue(
+    manager: Arc<Songbird>,
+    guild_id: GuildId,
+    (from, to): (usize, usize),
+    force: bool,
+) -> Result<String, BotError> {
+    let Some(handler_lock) = manager.get(guild_id) else {
+        return Err(BotError::NotConnected);
+    };
+    let handler = handler_lock.lock().await;
+    let q = handler.queue();
+
+    guard_current(q, from == 1 || to == 1, force).await?;
+
+    let moved = q.modify_queue(|q| {
+        let from = queue_index(from, q.len())?;
+        let to = queue_index(to, q.len())?;
+
+        let previous_head = q.front().map(|track| track.handle());
+        let track = q.remove(from).expect("index is within the queue");
+        let handle = track.handle();
+        q.insert(to, track);
+        sync_head(q, previous_head);
+
+        Ok::<_, BotError>(handle)
+    })?;
+
+    Ok(track_title(&moved).await.unwrap_or_default())
+}
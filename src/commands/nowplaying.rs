@@ -0,0 +1,241 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::{
+    all::GuildId,
+    builder::{
+        CreateActionRow, CreateButton, CreateEmbed, CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+    },
+    model::Colour,
+};
+use songbird::Songbird;
+use tracing::error;
+
+use super::{
+    common::{format_timestamp, guild_settings, is_playing, locale},
+    loop_mode::set_loop_mode,
+    pause::pause_song,
+    skip::skip_tracks,
+    BotError, Context, Error,
+};
+use crate::{
+    guild::{GuildSettingsStore, LoopMode},
+    i18n::{t, Locale},
+    player::SongMetadataKey,
+};
+
+const PROGRESS_BAR_SIZE: usize = 20;
+/// How long the buttons keep working after the command is used
+const CONTROLS_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+/// How often the progress bar is updated
+const REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+const BUTTON_PAUSE: &str = "nowplaying:pause";
+const BUTTON_SKIP: &str = "nowplaying:skip";
+const BUTTON_LOOP: &str = "nowplaying:loop";
+
+/// Shows the song that is playing now
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("pt-BR", "Mostra a música que está tocando agora")
+)]
+pub async fn nowplaying(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird Voice client placed in at initialisation.");
+    let settings = guild_settings(ctx).await;
+    let locale = locale(ctx).await;
+
+    let (embed, components) = now_playing(manager.clone(), &settings, guild_id, locale).await?;
+
+    let reply = ctx
+        .send(CreateReply::default().embed(embed).components(components))
+        .await?;
+    let message = reply.message().await?;
+
+    let started_at = Instant::now();
+    while started_at.elapsed() < CONTROLS_TIMEOUT {
+        let interaction = message
+            .await_component_interaction(ctx)
+            .timeout(REFRESH_INTERVAL)
+            .await;
+
+        if let Some(interaction) = &interaction {
+            let result = match interaction.data.custom_id.as_str() {
+                BUTTON_PAUSE => toggle_pause(manager.clone(), guild_id).await,
+                BUTTON_SKIP => skip_tracks(manager.clone(), guild_id, (0, 0))
+                    .await
+                    .map(|_| ()),
+                BUTTON_LOOP => {
+                    let mode = match settings.get(guild_id).await.loop_mode {
+                        LoopMode::Off => LoopMode::Track,
+                        LoopMode::Track => LoopMode::Queue,
+                        LoopMode::Queue => LoopMode::Off,
+                    };
+                    set_loop_mode(manager.clone(), settings.clone(), guild_id, mode).await
+                }
+                _ => Ok(()),
+            };
+            if let Err(cause) = result {
+                error!(%cause, "failed to handle the now playing controls");
+            }
+        }
+
+        let Ok((embed, components)) =
+            now_playing(manager.clone(), &settings, guild_id, locale).await
+        else {
+            // the button must still be answered, such as when the last
+            // track was skipped
+            if let Some(interaction) = interaction {
+                let response = CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new().components(Vec::new()),
+                );
+                if let Err(cause) = interaction.create_response(ctx, response).await {
+                    error!(%cause, "failed to answer the now playing controls");
+                }
+            }
+            break;
+        };
+
+        let result = match interaction {
+            Some(interaction) => interaction
+                .create_response(
+                    ctx,
+                    CreateInteractionResponse::UpdateMessage(
+                        CreateInteractionResponseMessage::new()
+                            .embed(embed)
+                            .components(components),
+                    ),
+                )
+                .await
+                .map_err(Error::from),
+            None => reply
+                .edit(
+                    ctx,
+                    CreateReply::default().embed(embed).components(components),
+                )
+                .await
+                .map_err(Error::from),
+        };
+        if let Err(cause) = result {
+            error!(%cause, "failed to update the now playing message");
+            break;
+        }
+    }
+
+    // the controls stop working once the loop ends
+    if let Err(cause) = reply
+        .edit(ctx, CreateReply::default().components(Vec::new()))
+        .await
+    {
+        error!(%cause, "failed to remove the now playing controls");
+    }
+
+    Ok(())
+}
+
+async fn toggle_pause(manager: Arc<Songbird>, guild_id: GuildId) -> Result<(), BotError> {
+    let current = match manager.get(guild_id) {
+        Some(handler_lock) => handler_lock.lock().await.queue().current(),
+        None => None,
+    };
+    let unpause = match current {
+        Some(current) => !is_playing(&current).await,
+        None => false,
+    };
+
+    pause_song(manager, guild_id, unpause).await
+}
+
+/// Builds the embed and the controls describing the track at the head of the
+/// queue.
+async fn now_playing(
+    manager: Arc<Songbird>,
+    settings: &GuildSettingsStore,
+    guild_id: GuildId,
+    locale: Locale,
+) -> Result<(CreateEmbed, Vec<CreateActionRow>), BotError> {
+    let Some(handler_lock) = manager.get(guild_id) else {
+        return Err(BotError::NotConnected);
+    };
+    let Some(current) = handler_lock.lock().await.queue().current() else {
+        return Err(BotError::NothingPlaying);
+    };
+
+    let playing = is_playing(&current).await;
+    let position = current
+        .get_info()
+        .await
+        .map(|info| info.position)
+        .unwrap_or_default();
+
+    let map = current.typemap().read().await;
+    let Some(meta) = map.get::<SongMetadataKey>() else {
+        return Err(BotError::NothingPlaying);
+    };
+
+    let progress = if meta.duration.is_zero() {
+        format!(
+            "{} / {}",
+            format_timestamp(position),
+            t!(locale, "player.live")
+        )
+    } else {
+        format!(
+            "{}\n{} / {}",
+            progress_bar(position, meta.duration),
+            format_timestamp(position),
+            format_timestamp(meta.duration)
+        )
+    };
+
+    let mut embed = CreateEmbed::new()
+        .colour(Colour::BLUE)
+        .field(t!(locale, "player.now_playing"), meta.title.clone(), false)
+        .field("", progress, false)
+        .field(
+            "",
+            t!(locale, "player.requested_by", user = meta.user),
+            false,
+        );
+
+    let loop_mode = settings.get(guild_id).await.loop_mode;
+    if let Some(label) = loop_mode.label(locale) {
+        embed = embed.field("", label, false);
+    }
+
+    if let Some(thumb) = meta.thumbnail.clone() {
+        embed = embed.thumbnail(thumb);
+    }
+
+    let components = vec![CreateActionRow::Buttons(vec![
+        CreateButton::new(BUTTON_PAUSE).label(if playing {
+            t!(locale, "nowplaying.pause")
+        } else {
+            t!(locale, "nowplaying.resume")
+        }),
+        CreateButton::new(BUTTON_SKIP).label(t!(locale, "nowplaying.skip")),
+        CreateButton::new(BUTTON_LOOP).label(match loop_mode {
+            LoopMode::Off => t!(locale, "nowplaying.loop_off"),
+            LoopMode::Track => t!(locale, "nowplaying.loop_track"),
+            LoopMode::Queue => t!(locale, "nowplaying.loop_queue"),
+        }),
+    ])];
+
+    Ok((embed, components))
+}
+
+fn progress_bar(position: Duration, duration: Duration) -> String {
+    let progress = position.as_secs_f64() / duration.as_secs_f64();
+    let filled = ((progress * PROGRESS_BAR_SIZE as f64) as usize).min(PROGRESS_BAR_SIZE - 1);
+
+    (0..PROGRESS_BAR_SIZE)
+        .map(|i| if i == filled { "🔘" } else { "▬" })
+        .collect()
+}
@@ -4,55 +4,60 @@ use serenity::all::GuildId;
 use songbird::Songbird;
 use tracing::error;
 
-use super::{Context, Error};
+use super::{common::locale, BotError, Context, Error};
+use crate::i18n::t;
 
-/// Pausa a música que está tocando
-#[poise::command(slash_command, guild_only, subcommands("on", "off"))]
+/// Pauses the song that is playing
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("on", "off"),
+    description_localized("pt-BR", "Pausa a música que está tocando")
+)]
 pub async fn pause(ctx: Context<'_>) -> Result<(), Error> {
-    let guild_id = ctx.guild_id().expect("Guild ID is not available");
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
     let manager = songbird::get(ctx.serenity_context())
         .await
         .expect("Songbird Voice client placed in at initialisation.");
 
-    if let Err(err) = pause_song(manager, guild_id, false).await {
-        ctx.reply(err.to_string()).await?;
-    } else {
-        ctx.reply("Música pausada").await?;
-    }
+    pause_song(manager, guild_id, false).await?;
+    ctx.reply(t!(locale(ctx).await, "pause.paused")).await?;
 
     Ok(())
 }
 
-/// Pausa a música que está tocando
-#[poise::command(prefix_command, slash_command)]
+/// Pauses the song that is playing
+#[poise::command(
+    prefix_command,
+    slash_command,
+    description_localized("pt-BR", "Pausa a música que está tocando")
+)]
 async fn on(ctx: Context<'_>) -> Result<(), Error> {
-    let guild_id = ctx.guild_id().expect("Guild ID is not available");
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
     let manager = songbird::get(ctx.serenity_context())
         .await
         .expect("Songbird Voice client placed in at initialisation.");
 
-    if let Err(err) = pause_song(manager, guild_id, false).await {
-        ctx.reply(err.to_string()).await?;
-    } else {
-        ctx.reply("Música pausada").await?;
-    }
+    pause_song(manager, guild_id, false).await?;
+    ctx.reply(t!(locale(ctx).await, "pause.paused")).await?;
 
     Ok(())
 }
 
-/// Continua a tocar a música de onde parou
-#[poise::command(prefix_command, slash_command)]
+/// Resumes the song from where it stopped
+#[poise::command(
+    prefix_command,
+    slash_command,
+    description_localized("pt-BR", "Continua a tocar a música de onde parou")
+)]
 async fn off(ctx: Context<'_>) -> Result<(), Error> {
-    let guild_id = ctx.guild_id().expect("Guild ID is not available");
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
     let manager = songbird::get(ctx.serenity_context())
         .await
         .expect("Songbird Voice client placed in at initialisation.");
 
-    if let Err(err) = pause_song(manager, guild_id, true).await {
-        ctx.reply(err.to_string()).await?;
-    } else {
-        ctx.reply("Música despausada").await?;
-    }
+    pause_song(manager, guild_id, true).await?;
+    ctx.reply(t!(locale(ctx).await, "pause.resumed")).await?;
 
     Ok(())
 }
@@ -61,23 +66,23 @@ pub(super) async fn pause_song(
     manager: Arc<Songbird>,
     guild_id: GuildId,
     unpause: bool,
-) -> Result<(), anyhow::Error> {
+) -> Result<(), BotError> {
     let handler_lock = match manager.get(guild_id) {
         Some(handler) => handler,
-        None => return Err(anyhow::anyhow!("Não estou em nenhum canal")),
+        None => return Err(BotError::NotConnected),
     };
     let handler = handler_lock.lock().await;
 
     if unpause {
         if let Err(cause) = handler.queue().resume() {
             error!(%cause, "failed to unpause");
-            return Err(anyhow::anyhow!("Não consegui despausar"));
+            return Err(BotError::ResumeFailed);
         }
         Ok(())
     } else {
         if let Err(cause) = handler.queue().pause() {
             error!(%cause, "failed to pause");
-            return Err(anyhow::anyhow!("Não consegui pausar"));
+            return Err(BotError::PauseFailed);
         }
         Ok(())
     }
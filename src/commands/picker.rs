@@ -0,0 +1,349 @@
+use std::time::Instant;
+
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::{
+    all::{ButtonStyle, ComponentInteraction, ComponentInteractionDataKind},
+    builder::{
+        CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter, CreateInteractionResponse,
+        CreateInteractionResponseMessage, CreateSelectMenu, CreateSelectMenuKind,
+        CreateSelectMenuOption,
+    },
+    model::Colour,
+};
+use tracing::{error, info};
+
+use super::{
+    common::{config, format_timestamp, locale, truncate},
+    BotError, Context, Error,
+};
+use crate::{
+    i18n::{t, Locale},
+    tools::piped::{PipedClient, StreamItem},
+};
+
+/// How many results are shown at once
+const PAGE_SIZE: usize = 5;
+/// Discord's limit for the labels and descriptions of the menu's options
+const OPTION_TEXT_SIZE: usize = 100;
+/// Keeps the descriptions of a page within Discord's 1024 characters for the
+/// embed's field
+const MAX_TITLE_LENGTH: usize = 70;
+const MAX_UPLOADER_LENGTH: usize = 40;
+
+const PICKER_SELECT: &str = "picker:select";
+const PICKER_PREVIOUS: &str = "picker:previous";
+const PICKER_NEXT: &str = "picker:next";
+const PICKER_CANCEL: &str = "picker:cancel";
+
+/// The search results fetched so far, with the token for the next ones
+struct Results {
+    streams: Vec<StreamItem>,
+    nextpage: Option<String>,
+}
+
+/// Searches for `query` and lets the author of the command pick one or more
+/// of the results, a page at a time.
+///
+/// Returns nothing if the author cancels or takes longer than the configured
+/// timeout. The picker message is deleted in every case.
+pub(super) async fn pick_songs(
+    ctx: Context<'_>,
+    client: &PipedClient<'_>,
+    query: &str,
+) -> Result<Vec<StreamItem>, Error> {
+    let locale = locale(ctx).await;
+    let timeout = config(ctx).await.picker_timeout;
+
+    let results = client
+        .search_songs(query)
+        .await
+        .map_err(BotError::SearchFailed)?;
+    let mut results = Results {
+        nextpage: results.nextpage.clone(),
+        streams: results.into_streams().collect(),
+    };
+
+    info!("found {} results", results.streams.len());
+
+    if results.streams.is_empty() {
+        return Err(BotError::NoResults.into());
+    }
+
+    let mut page = 0;
+    let (embed, components) = picker_page(locale, &results, page);
+    let reply = ctx
+        .send(
+            CreateReply::default()
+                .embed(embed)
+                .components(components)
+                .ephemeral(true),
+        )
+        .await?;
+    let message = reply.message().await?;
+
+    let deadline = Instant::now() + timeout;
+    let mut picked = Vec::new();
+    loop {
+        let Some(interaction) = message
+            .await_component_interaction(ctx)
+            .timeout(deadline.saturating_duration_since(Instant::now()))
+            .await
+        else {
+            info!("the search picker timed out");
+            break;
+        };
+
+        if interaction.user.id != ctx.author().id {
+            reject(ctx, &interaction, locale).await;
+            continue;
+        }
+
+        match interaction.data.custom_id.as_str() {
+            PICKER_SELECT => {
+                if let ComponentInteractionDataKind::StringSelect { values } =
+                    &interaction.data.kind
+                {
+                    picked = values
+                        .iter()
+                        .filter_map(|value| value.parse::<usize>().ok())
+                        .collect();
+                }
+                acknowledge(ctx, &interaction).await;
+                break;
+            }
+            PICKER_CANCEL => {
+                acknowledge(ctx, &interaction).await;
+                break;
+            }
+            PICKER_PREVIOUS => page = page.saturating_sub(1),
+            PICKER_NEXT => {
+                if (page + 1) * PAGE_SIZE >= results.streams.len() {
+                    load_more(client, query, &mut results).await;
+                }
+                if (page + 1) * PAGE_SIZE < results.streams.len() {
+                    page += 1;
+                }
+            }
+            _ => {}
+        }
+
+        let (embed, components) = picker_page(locale, &results, page);
+        let response = CreateInteractionResponse::UpdateMessage(
+            CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .components(components),
+        );
+        if let Err(cause) = interaction.create_response(ctx, response).await {
+            error!(%cause, "failed to update the search picker");
+        }
+    }
+
+    if let Err(cause) = reply.delete(ctx).await {
+        error!(%cause, "failed to delete the search picker");
+    }
+
+    // the results are kept in the order they were shown
+    picked.sort_unstable();
+    picked.dedup();
+    let mut streams = results.streams.into_iter().map(Some).collect::<Vec<_>>();
+    Ok(picked
+        .into_iter()
+        .filter_map(|index| streams.get_mut(index).and_then(Option::take))
+        .collect())
+}
+
+/// Fetches the next results of the search, if there are any.
+async fn load_more(client: &PipedClient<'_>, query: &str, results: &mut Results) {
+    let Some(nextpage) = results.nextpage.take() else {
+        return;
+    };
+
+    match client.search_songs_page(query, &nextpage).await {
+        Ok(more) => {
+            results.nextpage = more.nextpage.clone();
+            results.streams.extend(more.into_streams());
+        }
+        Err(cause) => {
+            error!(%cause, "failed to load more search results");
+            results.nextpage = Some(nextpage);
+        }
+    }
+}
+
+async fn acknowledge(ctx: Context<'_>, interaction: &ComponentInteraction) {
+    if let Err(cause) = interaction
+        .create_response(ctx, CreateInteractionResponse::Acknowledge)
+        .await
+    {
+        error!(%cause, "failed to acknowledge the search picker");
+    }
+}
+
+/// Tells someone other than the author that they can't use the picker.
+async fn reject(ctx: Context<'_>, interaction: &ComponentInteraction, locale: Locale) {
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .content(t!(locale, "picker.not_yours"))
+            .ephemeral(true),
+    );
+    if let Err(cause) = interaction.create_response(ctx, response).await {
+        error!(%cause, "failed to reject a search picker interaction");
+    }
+}
+
+/// Builds the embed and the controls showing a page of the results.
+fn picker_page(
+    locale: Locale,
+    results: &Results,
+    page: usize,
+) -> (CreateEmbed, Vec<CreateActionRow>) {
+    let start = page * PAGE_SIZE;
+    let streams = results
+        .streams
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(PAGE_SIZE);
+
+    let description = streams
+        .clone()
+        .map(|(i, result)| describe_result(locale, i + 1, result))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let embed = CreateEmbed::new()
+        .field(t!(locale, "play.results"), description, false)
+        .footer(CreateEmbedFooter::new(t!(
+            locale,
+            "picker.page",
+            page = page + 1
+        )))
+        .colour(Colour::RED);
+
+    let options = streams
+        .clone()
+        .map(|(i, result)| {
+            CreateSelectMenuOption::new(
+                truncate(&format!("{}. {}", i + 1, result.title), OPTION_TEXT_SIZE),
+                i.to_string(),
+            )
+            .description(truncate(
+                &summarize_result(locale, result),
+                OPTION_TEXT_SIZE,
+            ))
+        })
+        .collect::<Vec<_>>();
+    let count = options.len() as u8;
+    let select = CreateSelectMenu::new(PICKER_SELECT, CreateSelectMenuKind::String { options })
+        .placeholder(t!(locale, "picker.placeholder"))
+        .min_values(1)
+        .max_values(count);
+
+    let has_next = start + PAGE_SIZE < results.streams.len() || results.nextpage.is_some();
+    let buttons = vec![
+        CreateButton::new(PICKER_PREVIOUS)
+            .label(t!(locale, "picker.previous"))
+            .disabled(page == 0),
+        CreateButton::new(PICKER_NEXT)
+            .label(t!(locale, "picker.next"))
+            .disabled(!has_next),
+        CreateButton::new(PICKER_CANCEL)
+            .label(t!(locale, "picker.cancel"))
+            .style(ButtonStyle::Danger),
+    ];
+
+    (
+        embed,
+        vec![
+            CreateActionRow::SelectMenu(select),
+            CreateActionRow::Buttons(buttons),
+        ],
+    )
+}
+
+/// Describes a search result for the picker, with its uploader and views.
+fn describe_result(locale: Locale, position: usize, result: &StreamItem) -> String {
+    let mut details = vec![match result.duration() {
+        Some(duration) => format_timestamp(duration),
+        None => t!(locale, "player.live"),
+    }];
+    if result.is_short {
+        details.push(t!(locale, "play.short"));
+    }
+
+    let mut about = Vec::new();
+    if let Some(uploader) = &result.uploader_name {
+        about.push(truncate(uploader, MAX_UPLOADER_LENGTH));
+    }
+    if let Some(views) = result.views() {
+        about.push(t!(locale, "play.views", views = format_count(views)));
+    }
+    if let Some(uploaded) = result.uploaded.filter(|uploaded| *uploaded > 0) {
+        // rendered by Discord as a relative time in the user's language
+        about.push(format!("<t:{}:R>", uploaded / 1000));
+    }
+
+    let description = format!(
+        "{position}. **{}** ({})",
+        truncate(&result.title, MAX_TITLE_LENGTH),
+        details.join(", ")
+    );
+    if about.is_empty() {
+        description
+    } else {
+        format!("{description}\n-# {}", about.join(" · "))
+    }
+}
+
+/// A single line about the result, for the menu's options.
+fn summarize_result(locale: Locale, result: &StreamItem) -> String {
+    let duration = match result.duration() {
+        Some(duration) => format_timestamp(duration),
+        None => t!(locale, "player.live"),
+    };
+
+    match &result.uploader_name {
+        Some(uploader) => format!("{duration} · {uploader}"),
+        None => duration,
+    }
+}
+
+/// Shortens large numbers, such as `1234567` to `1.2M`.
+fn format_count(count: u64) -> String {
+    if count < 1_000 {
+        return count.to_string();
+    }
+
+    let mut value = count as f64 / 1e3;
+    let mut unit = "K";
+    for next in ["M", "B"] {
+        // 999_950 would round up to 1000.0K
+        if (value * 10.0).round() < 10_000.0 {
+            break;
+        }
+        value /= 1e3;
+        unit = next;
+    }
+    format!("{value:.1}{unit}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_counts() {
+        assert_eq!(format_count(0), "0");
+        assert_eq!(format_count(999), "999");
+        assert_eq!(format_count(1_000), "1.0K");
+        assert_eq!(format_count(1_234_567), "1.2M");
+        assert_eq!(format_count(2_500_000_000), "2.5B");
+    }
+
+    #[test]
+    fn promotes_counts_rounded_to_next_unit() {
+        assert_eq!(format_count(999_949), "999.9K");
+        assert_eq!(format_count(999_950), "1.0M");
+        assert_eq!(format_count(999_999_999), "1.0B");
+        assert_eq!(format_count(5_000_000_000_000), "5000.0B");
+    }
+}
@@ -1,34 +1,41 @@
-use std::time::Duration;
-
-use serenity::{
-    builder::{CreateButton, CreateEmbed, CreateInteractionResponseFollowup},
-    model::Colour,
-    prelude::TypeMapKey,
-};
-use songbird::{
-    input::{Compose, YoutubeDl},
-    Event,
-};
-use tracing::{error, info};
+use reqwest::Url;
+use tracing::info;
 
 use crate::{
-    commands::join::join_channel,
-    events::track::PlayingSongNotifier,
-    service::HttpKey,
-    tools::piped::{PipedClient, PipedError},
+    commands::{
+        autocomplete::autocomplete_song,
+        common::{guild_settings, locale, player, resolve_context},
+        join::join_channel,
+        picker::pick_songs,
+    },
+    i18n::t,
+    player::{AudioSource, SongMetadata},
+    resolver::{Resolution, ResolveContext, Resolver, Resolvers},
+    tools::piped::PipedClient,
 };
 
-use super::{Context, Error};
+use super::{BotError, Context, Error};
 
-/// Toca uma música no canal de voz atual
-#[poise::command(slash_command, guild_only)]
+/// Plays a song in your voice channel
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("pt-BR", "Toca uma música no canal de voz atual")
+)]
 pub async fn play(
     ctx: Context<'_>,
-    #[description = "URL ou nome da música a ser tocada"] song: String,
+    #[description = "URL or name of the song to play"]
+    #[description_localized("pt-BR", "URL ou nome da música a ser tocada")]
+    #[name_localized("pt-BR", "música")]
+    #[autocomplete = "autocomplete_song"]
+    song: String,
 ) -> Result<(), Error> {
     println!("play command");
-    let guild_id = ctx.guild_id().expect("guild id not found");
-    let guild = guild_id.to_guild_cached(&ctx.cache()).unwrap().clone();
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    let guild = guild_id
+        .to_guild_cached(&ctx.cache())
+        .ok_or(BotError::NotInGuild)?
+        .clone();
 
     let manager = songbird::get(ctx.serenity_context())
         .await
@@ -37,218 +44,160 @@ pub async fn play(
 
     let handler_lock = match manager.get(guild_id) {
         Some(handler) => handler,
-        None => match join_channel(manager, &ctx, &guild).await {
-            Ok((handler, _)) => handler,
-            Err(cause) => {
-                error!(%cause, "failed to join channel");
-                ctx.reply(cause.to_string()).await?;
-                return Ok(());
-            }
-        },
+        None => join_channel(manager, &ctx, &guild).await?.0,
     };
 
     ctx.defer_ephemeral().await?;
 
-    let Ok(query) = QueryKind::try_from(song.as_str()) else {
-        ctx.reply("URL ou nome da música inválidos").await?;
-        return Ok(());
-    };
-
-    info!(?query, "searching for song");
+    let channel_id = ctx.channel_id();
+    guild_settings(ctx)
+        .await
+        .update(guild_id, |settings| {
+            settings.last_channel = Some(channel_id)
+        })
+        .await;
 
-    let http = {
-        let data = ctx.serenity_context().data.read().await;
-        data.get::<HttpKey>()
-            .expect("http client not found")
-            .clone()
+    let resolve_ctx = resolve_context(ctx).await;
+    let Some(resolution) = resolve_query(ctx, &resolve_ctx, &song).await? else {
+        return Ok(());
     };
 
-    let mut source = match query {
-        QueryKind::Url(url) => YoutubeDl::new_ytdl_like("/usr/bin/yt-dlp", http, url.to_string()),
-        QueryKind::Search(search) => {
-            let results = match PipedClient::new(&http).search_songs(search).await {
-                Ok(results) => results,
-                Err(err) => {
-                    match err {
-                        PipedError::Request => {
-                            ctx.reply("Não consegui pesquisar nenhuma música").await?;
-                        }
-                        PipedError::Unknown => {
-                            ctx.reply("Deu ruim :sob:").await?;
-                        }
-                    };
-                    return Ok(());
-                }
-            };
-
-            info!("found {} results", results.items.len());
-
-            let results_size = results.items.len().min(5);
-
-            let result_formats = results
-                .items
+    let locale = locale(ctx).await;
+    let message = match &resolution {
+        Resolution::Songs(songs) => {
+            let titles = songs
                 .iter()
-                .take(results_size)
-                .enumerate()
-                .map(|(i, result)| {
-                    let fmt_duration =
-                        humantime::format_duration(Duration::from_secs(result.duration))
-                            .to_string();
-                    format!("{}. {} ({})", i + 1, result.title, fmt_duration)
-                })
-                .collect::<Vec<String>>();
-
-            let mut followup = CreateInteractionResponseFollowup::new().add_embed(
-                CreateEmbed::new()
-                    .field("Resultados", result_formats.join("\n"), false)
-                    .colour(Colour::RED),
+                .map(|song_meta| song_meta.title.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            t!(locale, "play.adding", title = titles)
+        }
+        Resolution::Playlist { name, total, songs } => {
+            info!(name, songs = songs.len(), "enqueueing playlist");
+
+            let mut message = t!(
+                locale,
+                "play.adding_playlist",
+                count = songs.len(),
+                name = name
             );
-
-            for (i, result) in results.items.iter().take(results_size).enumerate() {
-                followup = followup
-                    .button(CreateButton::new(result.url.clone()).label((i + 1).to_string()))
-            }
-
-            info!("creating follow up message");
-            let poise::Context::Application(ref app_ctx) = ctx else {
-                return Ok(());
-            };
-
-            let Ok(message) = app_ctx
-                .interaction
-                .create_followup(&ctx.http(), followup)
-                .await
-            else {
-                ctx.reply("Deu ruim :sob:").await?;
-                return Ok(());
-            };
-
-            let Some(response) = message.await_component_interaction(&ctx).await else {
-                return Ok(());
-            };
-
-            let video_url = &response.data.custom_id;
-            info!("user selected {}", video_url);
-
-            let video_uri = format!("https://www.youtube.com/{video_url}");
-
-            if let Err(cause) = app_ctx
-                .interaction
-                .delete_followup(&ctx.http(), message.id)
-                .await
-            {
-                error!(%cause, "failed to delete response")
+            if *total > songs.len() {
+                message.push_str(&t!(
+                    locale,
+                    "play.playlist_limited",
+                    limit = resolve_ctx.max_playlist_size
+                ));
             }
-
-            YoutubeDl::new(http, video_uri)
+            message
         }
     };
-
-    // let _ = source.create_async().await.unwrap();
-
-    let meta = source.aux_metadata().await.ok();
-
-    dbg!(&meta);
-
-    let requester = ctx
-        .author()
-        .global_name
-        .clone()
-        .unwrap_or(ctx.author().name.clone());
-
-    let song_meta = if let Some(metadata) = meta {
-        SongMetadata {
-            title: metadata.title.unwrap_or(String::new()),
-            duration: metadata.duration.unwrap_or(Duration::ZERO),
-            thumbnail: metadata.thumbnail,
-            user: requester,
-        }
-    } else {
-        SongMetadata {
-            title: String::from(query),
-            thumbnail: None,
-            duration: Duration::ZERO,
-            user: requester,
-        }
-    };
-
     ctx.send(
         poise::CreateReply::default()
-            .content(format!("Adicionando ||{}|| à fila", song_meta.title))
+            .content(message)
             .ephemeral(false),
     )
     .await?;
 
-    let mut handler = handler_lock.lock().await;
-
-    let song = handler.enqueue_input(source.into()).await;
-
-    if let Err(cause) = song.add_event(
-        Event::Track(songbird::TrackEvent::Play),
-        PlayingSongNotifier {
-            channel_id: ctx.channel_id(),
-            http: ctx.serenity_context().http.clone(),
-            context: ctx.serenity_context().clone(),
-            title: song_meta.title.clone(),
-            username: ctx
-                .author()
-                .global_name
-                .clone()
-                .unwrap_or(ctx.author().name.clone()),
-            thumbnail: song_meta.thumbnail.clone(),
-        },
-    ) {
-        error!(%cause, "failed to create song event")
+    let player = player(ctx, handler_lock).await;
+    for song_meta in resolution.into_songs() {
+        player.enqueue(player.source(&song_meta), song_meta).await;
     }
 
-    let mut typemap = song.typemap().write().await;
-    typemap.insert::<SongMetadataKey>(song_meta);
-
     Ok(())
 }
 
-#[derive(Debug, Clone)]
-enum QueryKind<'s> {
-    Url(&'s str),
-    Search(&'s str),
+/// Finds the songs asked for in `song`, as `/play` does, letting the user
+/// pick from the results of a YouTube search.
+///
+/// Returns `None` if the user didn't pick any of the results.
+pub(super) async fn resolve_query(
+    ctx: Context<'_>,
+    resolve_ctx: &ResolveContext,
+    song: &str,
+) -> Result<Option<Resolution>, Error> {
+    let query = Query::parse(song).ok_or(BotError::InvalidQuery)?;
+
+    info!(?query, "searching for song");
+
+    let resolvers = Resolvers::default();
+
+    let resolution = match query {
+        Query::Url(url) => resolvers.resolve(&url, resolve_ctx).await?,
+        Query::Search(text) => match search_provider(&resolvers, text) {
+            Some((resolver, text)) => resolver.search(text, resolve_ctx).await?,
+            None => {
+                let text = text.strip_prefix("yt:").unwrap_or(text).trim();
+                let client = PipedClient::new(&resolve_ctx.http, &resolve_ctx.piped);
+                let picked = pick_songs(ctx, &client, text).await?;
+                if picked.is_empty() {
+                    return Ok(None);
+                }
+
+                // the search results already describe the songs, so yt-dlp
+                // doesn't need to be asked about them
+                let songs = picked
+                    .into_iter()
+                    .map(|result| {
+                        let url = format!("https://www.youtube.com{}", result.url);
+                        info!("user selected {}", url);
+
+                        // live streams have no duration
+                        let duration = result.duration().unwrap_or_default();
+                        SongMetadata {
+                            title: result.title,
+                            url,
+                            duration,
+                            thumbnail: result.thumbnail,
+                            user: resolve_ctx.requester.clone(),
+                            source: AudioSource::YtDlp,
+                            origin: None,
+                            uploader: result.uploader_name,
+                            autoplayed: false,
+                            chapters: None,
+                            cover: None,
+                        }
+                    })
+                    .collect();
+                Resolution::Songs(songs)
+            }
+        },
+    };
+
+    Ok(Some(resolution))
 }
 
-impl<'s> From<QueryKind<'s>> for String {
-    fn from(value: QueryKind<'s>) -> Self {
-        match value {
-            QueryKind::Url(s) => String::from(s),
-            QueryKind::Search(s) => String::from(s),
-        }
-    }
+/// Splits a search like `sc:artist song` into the resolver of its provider
+/// and the text to search for.
+pub(super) fn search_provider<'r, 's>(
+    resolvers: &'r Resolvers,
+    search: &'s str,
+) -> Option<(&'r dyn Resolver, &'s str)> {
+    let (prefix, text) = search.split_once(':')?;
+    let resolver = resolvers.for_prefix(&prefix.trim().to_lowercase())?;
+    Some((resolver, text.trim()))
+}
+
+/// What was asked for in `/play`
+#[derive(Debug, Clone)]
+enum Query<'s> {
+    Url(Url),
+    /// A search, which may start with the prefix of a provider
+    Search(&'s str),
 }
 
-impl<'s> TryFrom<&'s str> for QueryKind<'s> {
-    type Error = ();
-    fn try_from(value: &'s str) -> Result<Self, Self::Error> {
+impl<'s> Query<'s> {
+    fn parse(value: &'s str) -> Option<Self> {
+        let value = value.trim();
         if value.is_empty() {
-            return Err(());
+            return None;
         }
-        if value.starts_with("http://")
-            || value.starts_with("https://")
-            || value.starts_with("www.")
-        {
-            Ok(QueryKind::Url(value))
+
+        if value.starts_with("http://") || value.starts_with("https://") {
+            Url::parse(value).ok().map(Query::Url)
+        } else if value.starts_with("www.") {
+            Url::parse(&format!("https://{value}")).ok().map(Query::Url)
         } else {
-            Ok(QueryKind::Search(value))
+            Some(Query::Search(value))
         }
     }
 }
-
-pub struct SongMetadataKey;
-
-#[allow(dead_code)]
-pub struct SongMetadata {
-    pub title: String,
-    pub duration: Duration,
-    pub user: String,
-    pub thumbnail: Option<String>,
-}
-
-impl TypeMapKey for SongMetadataKey {
-    type Value = SongMetadata;
-}
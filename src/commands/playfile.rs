@@ -0,0 +1,152 @@
+use std::{sync::Arc, time::Duration};
+
+use poise::serenity_prelude as serenity;
+use reqwest::{header::RANGE, Client as HttpClient, Url};
+use tracing::{error, info};
+
+use crate::{
+    commands::{
+        common::{guild_settings, http_client, locale, player, requester},
+        join::join_channel,
+    },
+    i18n::t,
+    player::{AudioSource, SongMetadata},
+    resolver::{DirectResolver, Resolver},
+    tools::tags::{read_tags, FileTags},
+};
+
+use super::{BotError, Context, Error};
+
+/// How much of the start of the file is downloaded to read its tags, which
+/// is usually enough for the ones with cover art
+const HEADER_SIZE: usize = 4 * 1024 * 1024;
+
+/// Plays an audio file in your voice channel
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("pt-BR", "Toca um arquivo de áudio no canal de voz atual")
+)]
+pub async fn playfile(
+    ctx: Context<'_>,
+    #[description = "The audio file to play"]
+    #[description_localized("pt-BR", "O arquivo de áudio a ser tocado")]
+    #[name_localized("pt-BR", "arquivo")]
+    file: serenity::Attachment,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    let guild = guild_id
+        .to_guild_cached(&ctx.cache())
+        .ok_or(BotError::NotInGuild)?
+        .clone();
+
+    let is_audio = file
+        .content_type
+        .as_deref()
+        .is_some_and(|content_type| content_type.starts_with("audio/"))
+        || Url::parse(&file.url).is_ok_and(|url| DirectResolver.matches(&url));
+    if !is_audio {
+        return Err(BotError::NotAudioFile.into());
+    }
+
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird Voice client placed in at initialisation.")
+        .clone();
+
+    let handler_lock = match manager.get(guild_id) {
+        Some(handler) => handler,
+        None => join_channel(manager, &ctx, &guild).await?.0,
+    };
+
+    ctx.defer_ephemeral().await?;
+
+    let channel_id = ctx.channel_id();
+    guild_settings(ctx)
+        .await
+        .update(guild_id, |settings| {
+            settings.last_channel = Some(channel_id)
+        })
+        .await;
+
+    let extension = file
+        .filename
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_lowercase());
+
+    // only the start of the file is read to learn about it, songbird streams
+    // it again once it starts playing
+    let contents = download_header(&http_client(ctx).await, &file.url)
+        .await
+        .map_err(|cause| {
+            error!(%cause, url = file.url, "failed to download the attachment");
+            BotError::ResolveFailed
+        })?;
+    let complete = contents.len() >= file.size as usize;
+    let tags =
+        tokio::task::spawn_blocking(move || read_tags(contents, extension.as_deref(), complete))
+            .await?;
+    // some formats keep what's needed to probe them at the end of the file
+    if complete && tags.is_none() {
+        return Err(BotError::NotAudioFile.into());
+    }
+    let FileTags {
+        title,
+        artist,
+        duration,
+        cover,
+    } = tags.unwrap_or_default();
+
+    let title = match (&artist, title) {
+        (Some(artist), Some(title)) => format!("{artist} - {title}"),
+        (None, Some(title)) => title,
+        _ => match file.filename.rsplit_once('.') {
+            Some((name, _)) if !name.is_empty() => name.to_string(),
+            _ => file.filename.clone(),
+        },
+    };
+    info!(title, ?duration, "playing attachment");
+
+    ctx.reply(t!(locale(ctx).await, "play.adding", title = title))
+        .await?;
+
+    let song_meta = SongMetadata {
+        title,
+        url: file.url,
+        duration: duration.unwrap_or(Duration::ZERO),
+        thumbnail: None,
+        user: requester(ctx),
+        source: AudioSource::Http,
+        origin: None,
+        uploader: artist,
+        autoplayed: false,
+        chapters: None,
+        cover: cover.map(Arc::new),
+    };
+
+    let player = player(ctx, handler_lock).await;
+    player.enqueue(player.source(&song_meta), song_meta).await;
+
+    Ok(())
+}
+
+/// Downloads up to [`HEADER_SIZE`] bytes from the start of the file.
+async fn download_header(http: &HttpClient, url: &str) -> Result<Vec<u8>, reqwest::Error> {
+    let mut res = http
+        .get(url)
+        .header(RANGE, format!("bytes=0-{}", HEADER_SIZE - 1))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    // servers that ignore the range send the whole file
+    let mut contents = Vec::new();
+    while let Some(chunk) = res.chunk().await? {
+        contents.extend_from_slice(&chunk);
+        if contents.len() >= HEADER_SIZE {
+            contents.truncate(HEADER_SIZE);
+            break;
+        }
+    }
+    Ok(contents)
+}
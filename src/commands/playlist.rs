@@ -0,0 +1,460 @@
+use poise::{serenity_prelude as serenity, CreateReply};
+use reqwest::Url;
+use serenity::{
+    builder::CreateEmbed,
+    futures::{stream, StreamExt},
+    model::Colour,
+};
+use tracing::{info, warn};
+
+use super::{
+    autocomplete::autocomplete_song,
+    common::{
+        format_titles, guild_settings, locale, player, playlist_store, resolve_context, track_songs,
+    },
+    join::join_channel,
+    play::resolve_query,
+    BotError, Context, Error,
+};
+use crate::{
+    i18n::t,
+    player::SongMetadata,
+    playlists::{PlaylistOwner, PlaylistScope, PlaylistStore},
+    resolver::{ResolveContext, Resolvers},
+};
+
+/// How many songs of a playlist are resolved at the same time
+const LOAD_CONCURRENCY: usize = 4;
+/// How many playlist names are suggested
+const MAX_SUGGESTIONS: usize = 25;
+
+/// Saves the queue to be played again later
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands(
+        "playlist_save",
+        "playlist_load",
+        "playlist_list",
+        "playlist_delete",
+        "playlist_add"
+    ),
+    subcommand_required,
+    description_localized("pt-BR", "Salva a fila para ser tocada de novo depois")
+)]
+pub async fn playlist(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Saves the current queue as a playlist
+#[poise::command(
+    slash_command,
+    guild_only,
+    rename = "save",
+    name_localized("pt-BR", "salvar"),
+    description_localized("pt-BR", "Salva a fila atual como uma playlist")
+)]
+async fn playlist_save(
+    ctx: Context<'_>,
+    #[description = "The name of the playlist, replacing the one with the same name"]
+    #[description_localized("pt-BR", "O nome da playlist, substituindo a que tiver o mesmo nome")]
+    #[name_localized("pt-BR", "nome")]
+    #[max_length = 50]
+    name: String,
+    #[description = "Who can use the playlist, only you by default"]
+    #[description_localized("pt-BR", "Quem pode usar a playlist, só você por padrão")]
+    #[name_localized("pt-BR", "escopo")]
+    scope: Option<PlaylistScope>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird Voice client placed in at initialisation.");
+
+    let Some(handler_lock) = manager.get(guild_id) else {
+        return Err(BotError::NotConnected.into());
+    };
+    let queue = handler_lock.lock().await.queue().current_queue();
+    let songs = track_songs(queue.iter()).await;
+    if songs.is_empty() {
+        return Err(BotError::EmptyQueue.into());
+    }
+
+    let name = playlist_name(&name)?;
+    let count = songs.len();
+    let owner = owner(ctx, scope)?;
+    let store = playlist_store(ctx).await;
+    check_can_change(ctx, &store, owner, name).await?;
+    store.save(owner, name, songs, ctx.author().id).await;
+
+    info!(name, count, "saved playlist");
+    ctx.reply(t!(
+        locale(ctx).await,
+        "playlist.saved",
+        name = name,
+        count = count
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Adds a saved playlist to the queue
+#[poise::command(
+    slash_command,
+    guild_only,
+    rename = "load",
+    name_localized("pt-BR", "carregar"),
+    description_localized("pt-BR", "Adiciona uma playlist salva à fila")
+)]
+async fn playlist_load(
+    ctx: Context<'_>,
+    #[description = "The name of the playlist"]
+    #[description_localized("pt-BR", "O nome da playlist")]
+    #[name_localized("pt-BR", "nome")]
+    #[autocomplete = "autocomplete_playlist"]
+    name: String,
+    #[description = "Whose playlist it is, yours by default"]
+    #[description_localized("pt-BR", "De quem é a playlist, sua por padrão")]
+    #[name_localized("pt-BR", "escopo")]
+    scope: Option<PlaylistScope>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    let guild = guild_id
+        .to_guild_cached(&ctx.cache())
+        .ok_or(BotError::NotInGuild)?
+        .clone();
+
+    let name = name.trim();
+    let playlist = playlist_store(ctx)
+        .await
+        .get(owner(ctx, scope)?, name)
+        .await
+        .ok_or_else(|| BotError::PlaylistNotFound(name.to_string()))?;
+
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird Voice client placed in at initialisation.")
+        .clone();
+
+    let handler_lock = match manager.get(guild_id) {
+        Some(handler) => handler,
+        None => join_channel(manager, &ctx, &guild).await?.0,
+    };
+
+    ctx.defer().await?;
+
+    let channel_id = ctx.channel_id();
+    guild_settings(ctx)
+        .await
+        .update(guild_id, |settings| {
+            settings.last_channel = Some(channel_id)
+        })
+        .await;
+
+    // the songs are resolved again, as they may have been taken down since
+    // they were saved
+    let resolve_ctx = resolve_context(ctx).await;
+    let resolvers = Resolvers::default();
+    let resolutions = playlist
+        .songs
+        .iter()
+        .map(|saved| resolve_saved(&resolvers, &resolve_ctx, saved))
+        .collect::<Vec<_>>();
+    let songs = stream::iter(resolutions)
+        .buffered(LOAD_CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+    let skipped = playlist.songs.len() - songs.len();
+    info!(
+        name = playlist.name,
+        loaded = songs.len(),
+        skipped,
+        "loading playlist"
+    );
+    if songs.is_empty() {
+        return Err(BotError::EmptyPlaylist.into());
+    }
+
+    let locale = locale(ctx).await;
+    let mut message = t!(
+        locale,
+        "playlist.loading",
+        name = playlist.name,
+        count = songs.len()
+    );
+    if skipped > 0 {
+        message.push_str(&t!(locale, "playlist.skipped", count = skipped));
+    }
+    ctx.reply(message).await?;
+
+    let player = player(ctx, handler_lock).await;
+    for song_meta in songs {
+        player.enqueue(player.source(&song_meta), song_meta).await;
+    }
+
+    Ok(())
+}
+
+/// Lists the saved playlists
+#[poise::command(
+    slash_command,
+    guild_only,
+    rename = "list",
+    name_localized("pt-BR", "listar"),
+    description_localized("pt-BR", "Lista as playlists salvas")
+)]
+async fn playlist_list(
+    ctx: Context<'_>,
+    #[description = "Whose playlists to list, both yours and the server's by default"]
+    #[description_localized(
+        "pt-BR",
+        "De quem são as playlists listadas, suas e do servidor por padrão"
+    )]
+    #[name_localized("pt-BR", "escopo")]
+    scope: Option<PlaylistScope>,
+) -> Result<(), Error> {
+    let store = playlist_store(ctx).await;
+    let locale = locale(ctx).await;
+
+    let scopes = match scope {
+        Some(scope) => vec![scope],
+        None => vec![PlaylistScope::Personal, PlaylistScope::Guild],
+    };
+
+    let mut embed = CreateEmbed::new().colour(Colour::BLUE);
+    for scope in scopes {
+        let entries = store
+            .list(owner(ctx, Some(scope))?)
+            .await
+            .into_iter()
+            .map(|playlist| {
+                t!(
+                    locale,
+                    "playlist.entry",
+                    name = playlist.name,
+                    count = playlist.songs.len()
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let header = match scope {
+            PlaylistScope::Personal => t!(locale, "playlist.personal"),
+            PlaylistScope::Guild => t!(locale, "playlist.guild"),
+        };
+        let value = if entries.is_empty() {
+            t!(locale, "playlist.none")
+        } else {
+            format_titles(locale, &entries)
+        };
+        embed = embed.field(header, value, false);
+    }
+
+    ctx.send(CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Deletes a saved playlist
+#[poise::command(
+    slash_command,
+    guild_only,
+    rename = "delete",
+    name_localized("pt-BR", "apagar"),
+    description_localized("pt-BR", "Apaga uma playlist salva")
+)]
+async fn playlist_delete(
+    ctx: Context<'_>,
+    #[description = "The name of the playlist"]
+    #[description_localized("pt-BR", "O nome da playlist")]
+    #[name_localized("pt-BR", "nome")]
+    #[autocomplete = "autocomplete_playlist"]
+    name: String,
+    #[description = "Whose playlist it is, yours by default"]
+    #[description_localized("pt-BR", "De quem é a playlist, sua por padrão")]
+    #[name_localized("pt-BR", "escopo")]
+    scope: Option<PlaylistScope>,
+) -> Result<(), Error> {
+    let name = playlist_name(&name)?;
+    let owner = owner(ctx, scope)?;
+    let store = playlist_store(ctx).await;
+    check_can_change(ctx, &store, owner, name).await?;
+    let playlist = store
+        .delete(owner, name)
+        .await
+        .ok_or_else(|| BotError::PlaylistNotFound(name.to_string()))?;
+
+    info!(name = playlist.name, "deleted playlist");
+    ctx.reply(t!(
+        locale(ctx).await,
+        "playlist.deleted",
+        name = playlist.name
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Adds songs to a saved playlist, creating it if needed
+#[poise::command(
+    slash_command,
+    guild_only,
+    rename = "add",
+    name_localized("pt-BR", "adicionar"),
+    description_localized(
+        "pt-BR",
+        "Adiciona músicas a uma playlist salva, criando-a se necessário"
+    )
+)]
+async fn playlist_add(
+    ctx: Context<'_>,
+    #[description = "The name of the playlist"]
+    #[description_localized("pt-BR", "O nome da playlist")]
+    #[name_localized("pt-BR", "nome")]
+    #[autocomplete = "autocomplete_playlist"]
+    #[max_length = 50]
+    name: String,
+    #[description = "URL or name of the song to add"]
+    #[description_localized("pt-BR", "URL ou nome da música a ser adicionada")]
+    #[name_localized("pt-BR", "música")]
+    #[autocomplete = "autocomplete_song"]
+    song: String,
+    #[description = "Whose playlist it is, yours by default"]
+    #[description_localized("pt-BR", "De quem é a playlist, sua por padrão")]
+    #[name_localized("pt-BR", "escopo")]
+    scope: Option<PlaylistScope>,
+) -> Result<(), Error> {
+    let name = playlist_name(&name)?;
+    let owner = owner(ctx, scope)?;
+    let store = playlist_store(ctx).await;
+    check_can_change(ctx, &store, owner, name).await?;
+
+    ctx.defer_ephemeral().await?;
+
+    let resolve_ctx = resolve_context(ctx).await;
+    let Some(resolution) = resolve_query(ctx, &resolve_ctx, &song).await? else {
+        return Ok(());
+    };
+    let songs = resolution.into_songs();
+    let titles = songs
+        .iter()
+        .map(|song_meta| song_meta.title.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let count = store.append(owner, name, songs, ctx.author().id).await;
+
+    info!(name, count, "added songs to playlist");
+    ctx.reply(t!(
+        locale(ctx).await,
+        "playlist.added",
+        title = titles,
+        name = name,
+        count = count
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Resolves a saved song again, returning `None` if it can't be played
+/// anymore.
+async fn resolve_saved(
+    resolvers: &Resolvers,
+    resolve_ctx: &ResolveContext,
+    saved: &SongMetadata,
+) -> Option<SongMetadata> {
+    let url = Url::parse(&saved.url).ok()?;
+    let resolution = match resolvers.resolve(&url, resolve_ctx).await {
+        Ok(resolution) => resolution,
+        Err(cause) => {
+            warn!(%cause, url = saved.url, "skipping saved song");
+            return None;
+        }
+    };
+
+    // the song keeps the name it was saved with, which may come from
+    // another service
+    let mut song = resolution.into_songs().into_iter().next()?;
+    song.title = saved.title.clone();
+    song.origin = song.origin.or_else(|| saved.origin.clone());
+    Some(song)
+}
+
+/// Suggests the names of the playlists the user can use.
+async fn autocomplete_playlist(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let store = playlist_store(ctx).await;
+    let partial = partial.trim().to_lowercase();
+
+    let mut names = Vec::new();
+    for scope in [PlaylistScope::Personal, PlaylistScope::Guild] {
+        let Ok(owner) = owner(ctx, Some(scope)) else {
+            continue;
+        };
+        for playlist in store.list(owner).await {
+            if playlist.name.to_lowercase().contains(&partial) && !names.contains(&playlist.name) {
+                names.push(playlist.name);
+            }
+        }
+    }
+
+    names.truncate(MAX_SUGGESTIONS);
+    names
+}
+
+/// The owner of the playlists of `scope`, the author's by default.
+fn owner(ctx: Context<'_>, scope: Option<PlaylistScope>) -> Result<PlaylistOwner, BotError> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    Ok(PlaylistOwner::new(
+        scope.unwrap_or_default(),
+        ctx.author().id,
+        guild_id,
+    ))
+}
+
+/// The name given to a playlist, without the surrounding whitespace.
+fn playlist_name(name: &str) -> Result<&str, BotError> {
+    let name = name.trim();
+    if name.is_empty() {
+        Err(BotError::EmptyPlaylistName)
+    } else {
+        Ok(name)
+    }
+}
+
+/// Makes sure the author can replace, delete or add to the playlist `name`:
+/// the playlists of a guild can only be changed by whoever saved them or by
+/// the members who can manage the guild.
+async fn check_can_change(
+    ctx: Context<'_>,
+    store: &PlaylistStore,
+    owner: PlaylistOwner,
+    name: &str,
+) -> Result<(), BotError> {
+    if !matches!(owner, PlaylistOwner::Guild(_)) {
+        return Ok(());
+    }
+    let Some(playlist) = store.get(owner, name).await else {
+        return Ok(());
+    };
+    if playlist.created_by == Some(ctx.author().id) {
+        return Ok(());
+    }
+
+    // only the members of interactions come with their permissions
+    let can_manage = ctx
+        .author_member()
+        .await
+        .and_then(|member| member.permissions)
+        .is_some_and(|permissions| permissions.manage_guild());
+    if can_manage {
+        Ok(())
+    } else {
+        Err(BotError::PlaylistNotOwned(playlist.name))
+    }
+}
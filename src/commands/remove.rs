@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use serenity::all::GuildId;
+use songbird::Songbird;
+
+use super::{
+    common::{guard_current, locale, play_head, queue_index, track_title},
+    BotError, Context, Error,
+};
+use crate::i18n::t;
+
+/// Removes a song from the queue
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("pt-BR", "Remove uma música da fila de reprodução")
+)]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "The position of the song to remove"]
+    #[description_localized("pt-BR", "A posição da música a ser removida")]
+    #[name_localized("pt-BR", "posição")]
+    #[min = 1]
+    position: usize,
+    #[description = "Allows removing the song that is playing"]
+    #[description_localized("pt-BR", "Permite remover a música que está tocando")]
+    #[name_localized("pt-BR", "forçar")]
+    force: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird Voice client placed in at initialisation.");
+
+    let title = remove_track(manager, guild_id, position, force.unwrap_or(false)).await?;
+    ctx.reply(t!(locale(ctx).await, "remove.removing", title = title))
+        .await?;
+
+    Ok(())
+}
+
+pub(super) async fn remove_track(
+    manager: Arc<Songbird>,
+    guild_id: GuildId,
+    position: usize,
+    force: bool,
+) -> Result<String, BotError> {
+    let Some(handler_lock) = manager.get(guild_id) else {
+        return Err(BotError::NotConnected);
+    };
+    let handler = handler_lock.lock().await;
+    let q = handler.queue();
+
+    guard_current(q, position == 1, force).await?;
+
+    let removed = q.modify_queue(|q| {
+        let index = queue_index(position, q.len())?;
+        let track = q.remove(index).expect("index is within the queue");
+        track.stop().ok();
+
+        if index == 0 {
+            play_head(q);
+        }
+
+        Ok::<_, BotError>(track)
+    })?;
+
+    Ok(track_title(&removed).await.unwrap_or_default())
+}
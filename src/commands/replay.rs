@@ -0,0 +1,72 @@
+use tracing::info;
+
+use super::{
+    common::{guild_settings, locale, played_history, player, requester},
+    join::join_channel,
+    BotError, Context, Error,
+};
+use crate::i18n::t;
+
+/// Plays a song from the history again
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("pt-BR", "Toca de novo uma música do histórico")
+)]
+pub async fn replay(
+    ctx: Context<'_>,
+    #[description = "The position of the song in /history"]
+    #[description_localized("pt-BR", "A posição da música em /history")]
+    #[name_localized("pt-BR", "posição")]
+    #[min = 1]
+    position: usize,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    let guild = guild_id
+        .to_guild_cached(&ctx.cache())
+        .ok_or(BotError::NotInGuild)?
+        .clone();
+
+    let entries = played_history(ctx).await.get(guild_id).await;
+    if entries.is_empty() {
+        return Err(BotError::EmptyHistory.into());
+    }
+    let entry = position
+        .checked_sub(1)
+        .and_then(|index| entries.get(index))
+        .ok_or(BotError::InvalidPosition(position))?;
+
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird Voice client placed in at initialisation.")
+        .clone();
+
+    let handler_lock = match manager.get(guild_id) {
+        Some(handler) => handler,
+        None => join_channel(manager, &ctx, &guild).await?.0,
+    };
+
+    let channel_id = ctx.channel_id();
+    guild_settings(ctx)
+        .await
+        .update(guild_id, |settings| {
+            settings.last_channel = Some(channel_id)
+        })
+        .await;
+
+    let mut song_meta = entry.song.clone();
+    song_meta.user = requester(ctx);
+
+    info!(title = song_meta.title, position, "replaying song");
+    ctx.reply(t!(
+        locale(ctx).await,
+        "play.adding",
+        title = song_meta.title
+    ))
+    .await?;
+
+    let player = player(ctx, handler_lock).await;
+    player.enqueue(player.source(&song_meta), song_meta).await;
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use tracing::info;
+
+use super::{
+    common::{guild_settings, locale, player, session_store},
+    join::connect_channel,
+    BotError, Context, Error,
+};
+use crate::i18n::t;
+
+/// Rebuilds the queue that was playing before the bot restarted
+#[poise::command(
+    slash_command,
+    guild_only,
+    rename = "resume-session",
+    description_localized("pt-BR", "Recria a fila que tocava antes de o bot reiniciar")
+)]
+pub async fn resume_session(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    let session = session_store(ctx)
+        .await
+        .load(guild_id)
+        .await
+        .ok_or(BotError::NoSession)?;
+
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird Voice client placed in at initialisation.")
+        .clone();
+
+    if let Some(handler_lock) = manager.get(guild_id) {
+        if !handler_lock.lock().await.queue().is_empty() {
+            return Err(BotError::QueueNotEmpty.into());
+        }
+    }
+
+    ctx.defer().await?;
+
+    let handler_lock = connect_channel(manager, &ctx, guild_id, session.voice_channel).await?;
+
+    let channel_id = ctx.channel_id();
+    guild_settings(ctx)
+        .await
+        .update(guild_id, |settings| {
+            session.restore_settings(settings);
+            settings.last_channel = Some(channel_id);
+        })
+        .await;
+
+    info!(
+        guild_id = guild_id.get(),
+        tracks = session.tracks.len(),
+        "resuming session"
+    );
+    ctx.reply(t!(
+        locale(ctx).await,
+        "session.resumed",
+        count = session.tracks.len()
+    ))
+    .await?;
+
+    let player = player(ctx, handler_lock).await;
+    for (index, song_meta) in session.tracks.into_iter().enumerate() {
+        // the current track continues from where it stopped, unless it's
+        // a live stream
+        let position =
+            (index == 0 && session.position < song_meta.duration).then_some(session.position);
+
+        let track = player.enqueue(player.source(&song_meta), song_meta).await;
+        if let Some(position) = position.filter(|position| !position.is_zero()) {
+            let _ = track.seek(position);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,214 @@
+use std::{sync::Arc, time::Duration};
+
+use serenity::all::GuildId;
+use songbird::Songbird;
+use tracing::error;
+
+use super::{
+    common::{format_timestamp, locale},
+    BotError, Context, Error,
+};
+use crate::{i18n::t, player::SongMetadataKey};
+
+/// Seeks to a point of the current song
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("pt-BR", "Pula para um ponto da música atual")
+)]
+pub async fn seek(
+    ctx: Context<'_>,
+    #[description = "Where to seek to: 1:23, 83, +30s or -15s"]
+    #[description_localized("pt-BR", "Para onde pular: 1:23, 83, +30s ou -15s")]
+    #[name_localized("pt-BR", "tempo")]
+    timestamp: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird Voice client placed in at initialisation.");
+
+    let target = SeekTarget::parse(&timestamp).ok_or(BotError::InvalidTimestamp)?;
+
+    ctx.defer().await?;
+
+    let position = seek_current(manager, guild_id, target).await?;
+    ctx.reply(t!(
+        locale(ctx).await,
+        "seek.seeking",
+        position = format_timestamp(position)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Where to seek to, relative to the start of the track or to the current
+/// position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SeekTarget {
+    Absolute(Duration),
+    Forward(Duration),
+    Backward(Duration),
+}
+
+impl SeekTarget {
+    /// Parses timestamps like `1:23`, `1:02:03` and `83`, or offsets like
+    /// `+30s`, `-15s` and `+2m`.
+    pub(super) fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        let (kind, value): (fn(Duration) -> Self, &str) = match input.chars().next()? {
+            '+' => (Self::Forward, &input[1..]),
+            '-' => (Self::Backward, &input[1..]),
+            _ => (Self::Absolute, input),
+        };
+
+        parse_duration(value.trim()).map(kind)
+    }
+
+    fn resolve(self, position: Duration) -> Duration {
+        match self {
+            Self::Absolute(target) => target,
+            Self::Forward(offset) => position.saturating_add(offset),
+            Self::Backward(offset) => position.saturating_sub(offset),
+        }
+    }
+}
+
+fn parse_duration(value: &str) -> Option<Duration> {
+    if value.contains(':') {
+        let parts = value
+            .split(':')
+            .map(|part| part.parse::<u64>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        if parts.len() > 3 || parts.iter().skip(1).any(|&part| part >= 60) {
+            return None;
+        }
+        let secs = parts
+            .iter()
+            .try_fold(0u64, |secs, &part| secs.checked_mul(60)?.checked_add(part))?;
+        return Some(Duration::from_secs(secs));
+    }
+
+    let (number, unit) = match value.char_indices().find(|(_, c)| c.is_alphabetic()) {
+        Some((i, _)) => value.split_at(i),
+        None => (value, "s"),
+    };
+    let number = number.parse::<u64>().ok()?;
+
+    let multiplier = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        _ => return None,
+    };
+    number.checked_mul(multiplier).map(Duration::from_secs)
+}
+
+pub(super) async fn seek_current(
+    manager: Arc<Songbird>,
+    guild_id: GuildId,
+    target: SeekTarget,
+) -> Result<Duration, BotError> {
+    let Some(handler_lock) = manager.get(guild_id) else {
+        return Err(BotError::NotConnected);
+    };
+    let Some(current) = handler_lock.lock().await.queue().current() else {
+        return Err(BotError::NothingPlaying);
+    };
+
+    let duration = {
+        let map = current.typemap().read().await;
+        map.get::<SongMetadataKey>()
+            .map(|meta| meta.duration)
+            .unwrap_or_default()
+    };
+    if duration.is_zero() {
+        return Err(BotError::NotSeekable);
+    }
+
+    let position = current
+        .get_info()
+        .await
+        .map(|info| info.position)
+        .unwrap_or_default();
+    let target = target.resolve(position);
+
+    if target >= duration {
+        return Err(BotError::SeekOutOfRange(duration));
+    }
+
+    current.seek_async(target).await.map_err(|cause| {
+        error!(%cause, "failed to seek");
+        BotError::SeekFailed
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn parses_timestamps() {
+        assert_eq!(
+            SeekTarget::parse("1:23"),
+            Some(SeekTarget::Absolute(secs(83)))
+        );
+        assert_eq!(
+            SeekTarget::parse("83"),
+            Some(SeekTarget::Absolute(secs(83)))
+        );
+        assert_eq!(
+            SeekTarget::parse("1:02:03"),
+            Some(SeekTarget::Absolute(secs(3723)))
+        );
+        assert_eq!(
+            SeekTarget::parse(" 2m "),
+            Some(SeekTarget::Absolute(secs(120)))
+        );
+    }
+
+    #[test]
+    fn parses_offsets() {
+        assert_eq!(
+            SeekTarget::parse("+30s"),
+            Some(SeekTarget::Forward(secs(30)))
+        );
+        assert_eq!(
+            SeekTarget::parse("-15s"),
+            Some(SeekTarget::Backward(secs(15)))
+        );
+        assert_eq!(
+            SeekTarget::parse("+1:00"),
+            Some(SeekTarget::Forward(secs(60)))
+        );
+    }
+
+    #[test]
+    fn resolves_offsets_from_position() {
+        let backward = SeekTarget::parse("-15s").unwrap();
+        assert_eq!(backward.resolve(secs(40)), secs(25));
+        assert_eq!(backward.resolve(Duration::ZERO), Duration::ZERO);
+
+        let forward = SeekTarget::parse("+30s").unwrap();
+        assert_eq!(forward.resolve(secs(40)), secs(70));
+    }
+
+    #[test]
+    fn rejects_overflowing_timestamps() {
+        assert_eq!(SeekTarget::parse("18446744073709551615h"), None);
+        assert_eq!(SeekTarget::parse("5124095576030432:00:00"), None);
+        assert_eq!(SeekTarget::parse("99999999999999999999"), None);
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        for input in ["1::2", "abc", "+", "-", "", "1:60", "1:2:3:4", "10x", "1.5"] {
+            assert_eq!(SeekTarget::parse(input), None, "{input:?}");
+        }
+    }
+}
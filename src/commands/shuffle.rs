@@ -0,0 +1,113 @@
+use std::{collections::HashMap, sync::Arc};
+
+use rand::seq::SliceRandom;
+use serenity::all::GuildId;
+use songbird::{tracks::Queued, Songbird};
+
+use super::{common::locale, BotError, Context, Error};
+use crate::{i18n::t, player::SongMetadataKey};
+
+#[derive(Debug, Clone, Copy, Default, poise::ChoiceParameter)]
+pub enum ShuffleMode {
+    /// Shuffles all the next songs
+    #[default]
+    #[name = "normal"]
+    Normal,
+    /// Interleaves the songs of each person that asked for them
+    #[name = "fair"]
+    #[name_localized("pt-BR", "justo")]
+    Fair,
+}
+
+/// Shuffles the next songs of the queue
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("pt-BR", "Embaralha as próximas músicas da fila")
+)]
+pub async fn shuffle(
+    ctx: Context<'_>,
+    #[description = "How to shuffle the songs"]
+    #[description_localized("pt-BR", "Como embaralhar as músicas")]
+    #[name_localized("pt-BR", "modo")]
+    mode: Option<ShuffleMode>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird Voice client placed in at initialisation.");
+
+    let count = shuffle_queue(manager, guild_id, mode.unwrap_or_default()).await?;
+    ctx.reply(t!(locale(ctx).await, "shuffle.shuffling", count = count))
+        .await?;
+
+    Ok(())
+}
+
+/// Shuffles every track but the one at the head of the queue, returning how
+/// many were shuffled.
+pub(super) async fn shuffle_queue(
+    manager: Arc<Songbird>,
+    guild_id: GuildId,
+    mode: ShuffleMode,
+) -> Result<usize, BotError> {
+    let Some(handler_lock) = manager.get(guild_id) else {
+        return Err(BotError::NotConnected);
+    };
+    let handler = handler_lock.lock().await;
+    let q = handler.queue();
+
+    // the requesters must be read beforehand, since the typemaps can't be
+    // awaited while the queue is locked
+    let mut requesters = HashMap::new();
+    if let ShuffleMode::Fair = mode {
+        for track in q.current_queue().iter().skip(1) {
+            let map = track.typemap().read().await;
+            if let Some(meta) = map.get::<SongMetadataKey>() {
+                requesters.insert(track.uuid(), meta.user.clone());
+            }
+        }
+    }
+
+    q.modify_queue(|q| {
+        if q.len() <= 2 {
+            return Err(BotError::NotEnoughTracks);
+        }
+
+        let mut upcoming = q.drain(1..).collect::<Vec<_>>();
+        let count = upcoming.len();
+        let mut rng = rand::thread_rng();
+
+        match mode {
+            ShuffleMode::Normal => upcoming.shuffle(&mut rng),
+            ShuffleMode::Fair => {
+                let mut groups: Vec<(Option<&String>, Vec<Queued>)> = Vec::new();
+                for track in upcoming.drain(..) {
+                    let user = requesters.get(&track.uuid());
+                    match groups.iter_mut().find(|(group, _)| *group == user) {
+                        Some((_, tracks)) => tracks.push(track),
+                        None => groups.push((user, vec![track])),
+                    }
+                }
+
+                groups.shuffle(&mut rng);
+                let mut groups = groups
+                    .into_iter()
+                    .map(|(_, mut tracks)| {
+                        tracks.shuffle(&mut rng);
+                        tracks.into_iter()
+                    })
+                    .collect::<Vec<_>>();
+
+                while upcoming.len() < count {
+                    for tracks in &mut groups {
+                        upcoming.extend(tracks.next());
+                    }
+                }
+            }
+        }
+
+        q.extend(upcoming);
+        Ok(count)
+    })
+}
@@ -1,219 +1,136 @@
 use std::sync::Arc;
 
-use serenity::{
-    all::{CommandInteraction, CommandOptionType, GuildId, ResolvedValue},
-    async_trait,
-    builder::{CreateCommand, CreateCommandOption},
-    client::Context,
-};
-
+use serenity::all::GuildId;
 use songbird::Songbird;
 use tracing::info;
 
-use crate::commands::common;
-
-/// Skip some songs
-pub struct Skip;
-
-#[async_trait]
-impl super::Command for Skip {
-    fn name(&self) -> String {
-        String::from("skip")
-    }
-
-    fn register(&self, cmd: CreateCommand) -> CreateCommand {
-        cmd.description("Pula algumas músicas")
-            .add_option(
-                CreateCommandOption::new(
-                    CommandOptionType::SubCommand,
-                    "first",
-                    "Pula a música atual",
-                )
-                .add_sub_option(
-                    CreateCommandOption::new(
-                        CommandOptionType::Integer,
-                        "count",
-                        "Pula as n primeiras músicas",
-                    )
-                    .min_int_value(1)
-                    .required(false),
-                )
-                .required(false)
-                .set_autocomplete(true),
-            )
-            .add_option(
-                CreateCommandOption::new(
-                    CommandOptionType::SubCommand,
-                    "position",
-                    "Pula a música na posição indicada",
-                )
-                .add_sub_option(
-                    CreateCommandOption::new(
-                        CommandOptionType::Integer,
-                        "at",
-                        "A posição da música a ser pulada",
-                    )
-                    .required(true)
-                    .min_int_value(1),
-                )
-                .required(false)
-                .set_autocomplete(true),
-            )
-            .add_option(
-                CreateCommandOption::new(
-                    CommandOptionType::SubCommand,
-                    "range",
-                    "Pula as músicas em no intervalo (inclusivo) indicado",
-                )
-                .add_sub_option(
-                    CreateCommandOption::new(
-                        CommandOptionType::Integer,
-                        "start",
-                        "O começo do intervalo",
-                    )
-                    .min_int_value(1)
-                    .required(true),
-                )
-                .add_sub_option(
-                    CreateCommandOption::new(
-                        CommandOptionType::Integer,
-                        "end",
-                        "O final do intervalo",
-                    )
-                    .min_int_value(2)
-                    .required(true),
-                )
-                .required(false)
-                .set_autocomplete(true),
-            )
-    }
-
-    async fn run(&self, ctx: Context, cmd: CommandInteraction) {
-        let options = cmd.data.options();
-        let option_first = common::get_option(&options, "first")
-            .and_then(|opt| {
-                if let ResolvedValue::SubCommand(sub) = opt {
-                    Some(sub)
-                } else {
-                    None
-                }
-            })
-            .map(|sub| {
-                common::get_option(sub, "count")
-                    .and_then(|val| {
-                        if let ResolvedValue::Integer(count) = val {
-                            Some(*count as usize)
-                        } else {
-                            None
-                        }
-                    })
-                    .unwrap_or(1)
-            });
-        let option_position = common::get_option(&options, "position")
-            .and_then(|opt| {
-                if let ResolvedValue::SubCommand(sub) = opt {
-                    Some(sub)
-                } else {
-                    None
-                }
-            })
-            .and_then(|sub| {
-                common::get_option(sub, "at").and_then(|val| {
-                    if let ResolvedValue::Integer(at) = val {
-                        Some(*at as usize)
-                    } else {
-                        None
-                    }
-                })
-            });
-        let option_range = common::get_option(&options, "range")
-            .and_then(|opt| {
-                if let ResolvedValue::SubCommand(sub) = opt {
-                    Some(sub)
-                } else {
-                    None
-                }
-            })
-            .map(|sub| {
-                let start = common::get_option(sub, "start").and_then(|val| {
-                    if let ResolvedValue::Integer(start) = val {
-                        Some(*start as usize)
-                    } else {
-                        None
-                    }
-                });
-                let end = common::get_option(sub, "end").and_then(|val| {
-                    if let ResolvedValue::Integer(start) = val {
-                        Some(*start as usize)
-                    } else {
-                        None
-                    }
-                });
-                (start.unwrap_or(1), end.unwrap_or(usize::MAX))
-            });
-
-        let mut range = (0, 0);
-        if let Some(r) = option_range {
-            range = r
-        }
-        if let Some(at) = option_position {
-            let at = at - 1;
-            range = (at, at);
-        }
-        if let Some(count) = option_first {
-            range = (0, count - 1)
-        }
+use super::{
+    common::{format_titles, locale, play_head, track_titles},
+    BotError, Context, Error,
+};
+use crate::i18n::t;
+
+/// Skips some songs
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("first", "position", "range"),
+    description_localized("pt-BR", "Pula algumas músicas")
+)]
+pub async fn skip(ctx: Context<'_>) -> Result<(), Error> {
+    skip_and_reply(ctx, (0, 0)).await
+}
 
-        let guild_id = common::get_guild_id(&ctx, &cmd);
-        let manager = songbird::get(&ctx)
-            .await
-            .expect("Songbird Voice client placed in at initialisation.");
+/// Skips the current song
+#[poise::command(slash_command, description_localized("pt-BR", "Pula a música atual"))]
+async fn first(
+    ctx: Context<'_>,
+    #[description = "Skips the first n songs"]
+    #[description_localized("pt-BR", "Pula as n primeiras músicas")]
+    #[name_localized("pt-BR", "quantidade")]
+    #[min = 1]
+    count: Option<usize>,
+) -> Result<(), Error> {
+    let count = count.unwrap_or(1).max(1);
+    skip_and_reply(ctx, (0, count - 1)).await
+}
 
-        match skip(manager, guild_id, range).await {
-            Ok(message) => common::respond(&ctx, &cmd, message),
-            Err(err) => common::respond(&ctx, &cmd, err),
-        }
+/// Skips the song at the given position
+#[poise::command(
+    slash_command,
+    description_localized("pt-BR", "Pula a música na posição indicada")
+)]
+async fn position(
+    ctx: Context<'_>,
+    #[description = "The position of the song to skip"]
+    #[description_localized("pt-BR", "A posição da música a ser pulada")]
+    #[name_localized("pt-BR", "em")]
+    #[min = 1]
+    at: usize,
+) -> Result<(), Error> {
+    let at = at.max(1) - 1;
+    skip_and_reply(ctx, (at, at)).await
+}
+
+/// Skips the songs in the given (inclusive) range
+#[poise::command(
+    slash_command,
+    description_localized("pt-BR", "Pula as músicas no intervalo (inclusivo) indicado")
+)]
+async fn range(
+    ctx: Context<'_>,
+    #[description = "The start of the range"]
+    #[description_localized("pt-BR", "O começo do intervalo")]
+    #[name_localized("pt-BR", "início")]
+    #[min = 1]
+    start: usize,
+    #[description = "The end of the range"]
+    #[description_localized("pt-BR", "O final do intervalo")]
+    #[name_localized("pt-BR", "fim")]
+    #[min = 1]
+    end: usize,
+) -> Result<(), Error> {
+    skip_and_reply(ctx, (start.max(1) - 1, end.max(1) - 1)).await
+}
+
+async fn skip_and_reply(ctx: Context<'_>, range: (usize, usize)) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    let manager = songbird::get(ctx.serenity_context())
         .await
-    }
+        .expect("Songbird Voice client placed in at initialisation.");
+
+    let titles = skip_tracks(manager, guild_id, range).await?;
+    let locale = locale(ctx).await;
+    ctx.reply(t!(
+        locale,
+        "skip.skipping",
+        count = titles.len(),
+        titles = format_titles(locale, &titles)
+    ))
+    .await?;
+
+    Ok(())
 }
 
-async fn skip(
+/// Removes the tracks in the 0-based, inclusive `range` from the queue and
+/// returns their titles.
+///
+/// If the track that is currently playing gets removed, the next one in the
+/// queue is started.
+pub(super) async fn skip_tracks(
     manager: Arc<Songbird>,
     guild_id: GuildId,
     range: (usize, usize),
-) -> Result<String, String> {
-    let handler_lock = match manager.get(guild_id) {
-        Some(handler) => handler,
-        None => return Err("Não estou em nenhum canal".to_string()),
+) -> Result<Vec<String>, BotError> {
+    let Some(handler_lock) = manager.get(guild_id) else {
+        return Err(BotError::NotConnected);
     };
     let handler = handler_lock.lock().await;
-    let q = handler.queue();
-
-    let (start, end) = (range.0, range.1.min(q.len()));
-    if end < start {
-        return Err("Intervalo inválido".to_string());
-    }
 
-    q.modify_queue(|q| {
+    let removed = handler.queue().modify_queue(|q| {
         info!("queue.len = {}", q.len());
 
-        assert!(end >= start);
+        if q.is_empty() {
+            return Err(BotError::EmptyQueue);
+        }
+
+        let (start, end) = (range.0, range.1.min(q.len() - 1));
+        if start > end {
+            return Err(BotError::InvalidRange);
+        }
 
-        let remove_range = start..=end;
-        let removed_current = !q.is_empty() && remove_range.contains(&0);
-        for i in remove_range {
-            if let Some(track) = q.remove(i) {
-                track.handle().stop().ok();
-            }
+        let removed = q.drain(start..=end).collect::<Vec<_>>();
+        for track in &removed {
+            track.stop().ok();
         }
 
         // play the next music if the one that was playing got removed
-        if !q.is_empty() && removed_current {
-            if let Some(track) = q.get_mut(0) {
-                track.play().ok();
-            }
+        if start == 0 {
+            play_head(q);
         }
-    });
 
-    Ok("Pulando algumas músicas".to_string())
+        Ok(removed)
+    })?;
+
+    Ok(track_titles(removed).await)
 }
@@ -0,0 +1,60 @@
+use super::{
+    common::{guild_settings, locale},
+    BotError, Context, Error,
+};
+use crate::{guild::SponsorCategory, i18n::t};
+
+/// Chooses which SponsorBlock segments are skipped in YouTube videos
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized(
+        "pt-BR",
+        "Escolhe quais trechos do SponsorBlock são pulados nos vídeos do YouTube"
+    )
+)]
+pub async fn sponsorblock(
+    ctx: Context<'_>,
+    #[description = "The kind of segment"]
+    #[description_localized("pt-BR", "O tipo de trecho")]
+    #[name_localized("pt-BR", "categoria")]
+    category: SponsorCategory,
+    #[description = "Whether the segments of this kind should be skipped"]
+    #[description_localized("pt-BR", "Se os trechos desse tipo devem ser pulados")]
+    #[name_localized("pt-BR", "pular")]
+    skip: bool,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+
+    let categories = guild_settings(ctx)
+        .await
+        .update(guild_id, |settings| {
+            let categories = &mut settings.sponsor_categories;
+            categories.retain(|&skipped| skipped != category);
+            if skip {
+                categories.push(category);
+            }
+            categories.clone()
+        })
+        .await;
+
+    let locale = locale(ctx).await;
+    let message = if categories.is_empty() {
+        t!(locale, "sponsorblock.off")
+    } else {
+        // listed in a stable order, whatever order they were chosen in
+        let labels = SponsorCategory::ALL
+            .into_iter()
+            .filter(|category| categories.contains(category))
+            .map(|category| category.label(locale))
+            .collect::<Vec<_>>();
+        t!(
+            locale,
+            "sponsorblock.skipping",
+            categories = labels.join(", ")
+        )
+    };
+    ctx.reply(message).await?;
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use serenity::all::GuildId;
+use songbird::{tracks::Queued, Songbird};
+
+use super::{
+    common::{guard_current, locale, queue_index, sync_head, track_title},
+    BotError, Context, Error,
+};
+use crate::i18n::t;
+
+/// Swaps the positions of two songs of the queue
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("pt-BR", "Troca duas músicas da fila de posição")
+)]
+pub async fn swap(
+    ctx: Context<'_>,
+    #[description = "The position of the first song"]
+    #[description_localized("pt-BR", "A posição da primeira música")]
+    #[min = 1]
+    a: usize,
+    #[description = "The position of the second song"]
+    #[description_localized("pt-BR", "A posição da segunda música")]
+    #[min = 1]
+    b: usize,
+    #[description = "Allows swapping the song that is playing"]
+    #[description_localized("pt-BR", "Permite trocar a música que está tocando")]
+    #[name_localized("pt-BR", "forçar")]
+    force: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird Voice client placed in at initialisation.");
+
+    let (title_a, title_b) =
+        swap_in_queue(manager, guild_id, (a, b), force.unwrap_or(false)).await?;
+    ctx.reply(t!(
+        locale(ctx).await,
+        "swap.swapping",
+        a = title_a,
+        b = title_b
+    ))
+    .await?;
+
+    Ok(())
+}
+
+pub(super) async fn swap_in_queue(
+    manager: Arc<Songbird>,
+    guild_id: GuildId,
+    (a, b): (usize, usize),
+    force: bool,
+) -> Result<(String, String), BotError> {
+    let Some(handler_lock) = manager.get(guild_id) else {
+        return Err(BotError::NotConnected);
+    };
+    let handler = handler_lock.lock().await;
+    let q = handler.queue();
+
+    guard_current(q, a == 1 || b == 1, force).await?;
+
+    let (track_a, track_b) = q.modify_queue(|q| {
+        let previous_head = q.front().map(|track| track.handle());
+        swap_entries(q, (a, b), Queued::handle, |q| sync_head(q, previous_head))
+    })?;
+
+    Ok((
+        track_title(&track_a).await.unwrap_or_default(),
+        track_title(&track_b).await.unwrap_or_default(),
+    ))
+}
+
+/// Swaps the entries at the 1-based positions `a` and `b`, returning what
+/// `read` says about them before `sync` runs, since it may discard the
+/// entries that fail to play.
+fn swap_entries<T, R>(
+    q: &mut VecDeque<T>,
+    (a, b): (usize, usize),
+    read: impl Fn(&T) -> R,
+    sync: impl FnOnce(&mut VecDeque<T>),
+) -> Result<(R, R), BotError> {
+    let a = queue_index(a, q.len())?;
+    let b = queue_index(b, q.len())?;
+
+    q.swap(a, b);
+    let swapped = (read(&q[b]), read(&q[a]));
+    sync(q);
+
+    Ok(swapped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue(entries: &[&'static str]) -> VecDeque<&'static str> {
+        entries.iter().copied().collect()
+    }
+
+    #[test]
+    fn swaps_entries() {
+        let mut q = queue(&["a", "b", "c"]);
+        let swapped = swap_entries(&mut q, (1, 3), |entry| *entry, |_| {}).unwrap();
+
+        assert_eq!(swapped, ("a", "c"));
+        assert_eq!(q, ["c", "b", "a"]);
+    }
+
+    #[test]
+    fn reads_entries_before_head_is_discarded() {
+        // the new head fails to play and is dropped, as `play_head` does
+        let mut q = queue(&["a", "b"]);
+        let swapped = swap_entries(
+            &mut q,
+            (1, 2),
+            |entry| *entry,
+            |q| {
+                q.pop_front();
+            },
+        )
+        .unwrap();
+
+        assert_eq!(swapped, ("a", "b"));
+        assert_eq!(q, ["a"]);
+    }
+
+    #[test]
+    fn rejects_invalid_positions() {
+        let mut q = queue(&["a", "b"]);
+
+        assert!(swap_entries(&mut q, (0, 1), |entry| *entry, |_| {}).is_err());
+        assert!(swap_entries(&mut q, (1, 3), |entry| *entry, |_| {}).is_err());
+        assert_eq!(q, ["a", "b"]);
+    }
+}
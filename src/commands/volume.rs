@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use serenity::all::GuildId;
+use songbird::Songbird;
+use tracing::error;
+
+use super::{
+    common::{guild_settings, locale},
+    BotError, Context, Error,
+};
+use crate::{guild::GuildSettingsStore, i18n::t};
+
+/// Changes the volume of the songs
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("pt-BR", "Muda o volume das músicas")
+)]
+pub async fn volume(
+    ctx: Context<'_>,
+    #[description = "The volume, in percent"]
+    #[description_localized("pt-BR", "O volume, em porcentagem")]
+    #[min = 0]
+    #[max = 200]
+    volume: u16,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(BotError::NotInGuild)?;
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird Voice client placed in at initialisation.");
+    let settings = guild_settings(ctx).await;
+
+    let volume = set_volume(manager, settings, guild_id, volume).await?;
+    ctx.reply(t!(locale(ctx).await, "volume.set", volume = volume))
+        .await?;
+
+    Ok(())
+}
+
+/// Stores the guild's volume and applies it to the tracks in the queue.
+pub(super) async fn set_volume(
+    manager: Arc<Songbird>,
+    settings: GuildSettingsStore,
+    guild_id: GuildId,
+    volume: u16,
+) -> Result<u16, BotError> {
+    let volume = volume.min(200);
+    let track_volume = settings
+        .update(guild_id, |settings| {
+            settings.volume = volume;
+            settings.track_volume()
+        })
+        .await;
+
+    let Some(handler_lock) = manager.get(guild_id) else {
+        return Ok(volume);
+    };
+    let handler = handler_lock.lock().await;
+
+    for track in handler.queue().current_queue() {
+        if let Err(cause) = track.set_volume(track_volume) {
+            error!(%cause, "failed to set the track volume");
+        }
+    }
+
+    Ok(volume)
+}
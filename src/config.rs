@@ -0,0 +1,95 @@
+use std::{path::PathBuf, time::Duration};
+
+use serenity::prelude::TypeMapKey;
+use shuttle_runtime::SecretStore;
+
+use crate::tools::piped::PIPED_URL;
+
+const DEFAULT_MAX_PLAYLIST_SIZE: usize = 50;
+const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+const DEFAULT_PIPED_COOLDOWN: Duration = Duration::from_secs(60);
+const DEFAULT_PICKER_TIMEOUT: Duration = Duration::from_secs(60);
+const DEFAULT_DATA_DIR: &str = "data";
+const DEFAULT_SESSION_SAVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Bot settings read from `Secrets.toml`
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// How many entries of a playlist are enqueued at most
+    pub max_playlist_size: usize,
+    /// How long the bot stays in a voice channel with an empty queue
+    pub idle_timeout: Duration,
+    /// The Piped API instances used for searches, in order of preference
+    pub piped_instances: Vec<String>,
+    /// How long a failing Piped instance is skipped
+    pub piped_cooldown: Duration,
+    /// How long the search results can be picked from
+    pub picker_timeout: Duration,
+    /// Where the state that survives restarts is stored
+    pub data_dir: PathBuf,
+    /// How often the queues are saved to be resumed after a restart
+    pub session_save_interval: Duration,
+}
+
+impl Config {
+    pub fn from_secrets(secrets: &SecretStore) -> Self {
+        Self {
+            max_playlist_size: parse_secret(secrets, "MAX_PLAYLIST_SIZE")
+                .unwrap_or(DEFAULT_MAX_PLAYLIST_SIZE),
+            idle_timeout: parse_secret(secrets, "IDLE_TIMEOUT_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_IDLE_TIMEOUT),
+            // a comma separated list, such as `https://a.example,https://b.example`
+            piped_instances: secrets
+                .get("PIPED_INSTANCES")
+                .map(|urls| {
+                    urls.split(',')
+                        .map(str::trim)
+                        .filter(|url| !url.is_empty())
+                        .map(String::from)
+                        .collect::<Vec<_>>()
+                })
+                .filter(|urls| !urls.is_empty())
+                .unwrap_or_else(|| vec![PIPED_URL.to_string()]),
+            piped_cooldown: parse_secret(secrets, "PIPED_COOLDOWN_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_PIPED_COOLDOWN),
+            picker_timeout: parse_secret(secrets, "PICKER_TIMEOUT_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_PICKER_TIMEOUT),
+            data_dir: secrets
+                .get("DATA_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from(DEFAULT_DATA_DIR)),
+            // a zero interval would stop the sessions from being saved
+            session_save_interval: parse_secret(secrets, "SESSION_SAVE_SECS")
+                .filter(|&secs| secs > 0)
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_SESSION_SAVE_INTERVAL),
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            max_playlist_size: DEFAULT_MAX_PLAYLIST_SIZE,
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            piped_instances: vec![PIPED_URL.to_string()],
+            piped_cooldown: DEFAULT_PIPED_COOLDOWN,
+            picker_timeout: DEFAULT_PICKER_TIMEOUT,
+            data_dir: PathBuf::from(DEFAULT_DATA_DIR),
+            session_save_interval: DEFAULT_SESSION_SAVE_INTERVAL,
+        }
+    }
+}
+
+fn parse_secret<T: std::str::FromStr>(secrets: &SecretStore, key: &str) -> Option<T> {
+    secrets.get(key).and_then(|value| value.parse().ok())
+}
+
+pub struct ConfigKey;
+
+impl TypeMapKey for ConfigKey {
+    type Value = Config;
+}
@@ -0,0 +1,150 @@
+use std::{collections::HashSet, time::Duration};
+
+use reqwest::Url;
+use serenity::async_trait;
+use songbird::{tracks::PlayMode, Event, EventContext, EventHandler};
+use tracing::{error, info};
+
+use crate::{
+    guild::LoopMode,
+    i18n,
+    player::{AudioSource, Player, SongMetadata},
+    resolver::video_id,
+    tools::piped::{PipedClient, StreamItem},
+};
+
+/// How many of the last played tracks can't be played again by autoplay
+const RECENT_TRACKS: usize = 50;
+/// How many of the last played tracks' uploaders are avoided by autoplay
+const RECENT_UPLOADERS: usize = 3;
+/// Longer videos are usually mixes or full albums
+const MAX_DURATION: Duration = Duration::from_secs(15 * 60);
+
+/// Enqueues a video related to the last played one once the queue runs out,
+/// if the guild turned autoplay on.
+pub struct Autoplayer {
+    pub player: Player,
+}
+
+#[async_trait]
+impl EventHandler for Autoplayer {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        let EventContext::Track(track_list) = ctx else {
+            return None;
+        };
+
+        // the queue never runs out while it loops
+        let settings = self.player.settings.get(self.player.guild_id).await;
+        if !settings.autoplay || settings.loop_mode == LoopMode::Queue {
+            return None;
+        }
+
+        for (state, handle) in track_list.iter() {
+            // the tracks that were stopped by hand, such as when the queue
+            // is cleared, shouldn't be followed by anything
+            if state.playing != PlayMode::End {
+                continue;
+            }
+
+            let queue = self.player.call.lock().await.queue().current_queue();
+            if queue.iter().all(|track| track.uuid() == handle.uuid()) {
+                self.play_related().await;
+            }
+        }
+
+        None
+    }
+}
+
+impl Autoplayer {
+    async fn play_related(&self) {
+        let guild_id = self.player.guild_id;
+        let history = self.player.history.get(guild_id).await;
+        let recent = &history[..history.len().min(RECENT_TRACKS)];
+
+        let Some(seed) = recent.iter().find_map(|entry| youtube_id(&entry.song.url)) else {
+            info!(guild_id = guild_id.get(), "no video to autoplay from");
+            return;
+        };
+
+        let client = PipedClient::new(&self.player.client, &self.player.piped);
+        let streams = match client.streams(&seed).await {
+            Ok(streams) => streams,
+            Err(cause) => {
+                error!(%cause, seed, "failed to fetch the related videos");
+                return;
+            }
+        };
+
+        let recent_ids = recent
+            .iter()
+            .filter_map(|entry| youtube_id(&entry.song.url))
+            .collect::<HashSet<_>>();
+        let mut recent_uploaders = recent
+            .iter()
+            .take(RECENT_UPLOADERS)
+            .filter_map(|entry| entry.song.uploader.clone())
+            .collect::<Vec<_>>();
+        // the uploader of the last video may only be known by Piped
+        recent_uploaders.extend(streams.uploader.clone());
+
+        let candidates = streams.into_related().collect::<Vec<_>>();
+        let Some(next) = pick_related(candidates, &recent_ids, &recent_uploaders) else {
+            info!(seed, "no related video to autoplay");
+            return;
+        };
+
+        info!(title = next.title, seed, "autoplaying related video");
+        let song_meta = SongMetadata {
+            url: format!("https://www.youtube.com{}", next.url),
+            duration: next.duration().unwrap_or_default(),
+            title: next.title,
+            thumbnail: next.thumbnail,
+            user: i18n::tr(self.player.locale, "autoplay.requester").to_string(),
+            source: AudioSource::YtDlp,
+            origin: None,
+            uploader: next.uploader_name,
+            autoplayed: true,
+            chapters: None,
+            cover: None,
+        };
+        self.player
+            .enqueue(self.player.source(&song_meta), song_meta)
+            .await;
+    }
+}
+
+/// Picks the most related of the `candidates` that wasn't played recently,
+/// preferring the ones whose uploader wasn't played recently either.
+fn pick_related(
+    candidates: Vec<StreamItem>,
+    recent_ids: &HashSet<String>,
+    recent_uploaders: &[String],
+) -> Option<StreamItem> {
+    let mut playable = candidates
+        .into_iter()
+        .filter(|candidate| {
+            !candidate.is_short
+                && candidate
+                    .duration()
+                    .is_some_and(|duration| duration <= MAX_DURATION)
+                && youtube_id(&format!("https://www.youtube.com{}", candidate.url))
+                    .is_some_and(|id| !recent_ids.contains(&id))
+        })
+        .collect::<Vec<_>>();
+
+    let fresh = playable.iter().position(|candidate| {
+        candidate
+            .uploader_name
+            .as_ref()
+            .map_or(true, |uploader| !recent_uploaders.contains(uploader))
+    });
+
+    // a repeated uploader is still better than silence
+    let index = fresh.or_else(|| (!playable.is_empty()).then_some(0))?;
+    Some(playable.swap_remove(index))
+}
+
+fn youtube_id(url: &str) -> Option<String> {
+    video_id(&Url::parse(url).ok()?)
+}
@@ -0,0 +1,147 @@
+use std::{sync::Mutex, time::Duration};
+
+use serenity::{async_trait, builder::CreateMessage, prelude::TypeMapKey};
+use songbird::{
+    tracks::{PlayMode, TrackHandle},
+    Event, EventContext, EventHandler,
+};
+use tracing::{error, info};
+
+use crate::{
+    i18n::t,
+    player::{Chapter, Player, SongMetadataKey},
+    tools::piped::PipedClient,
+};
+
+/// Fetches the chapters of a YouTube video from Piped once its track starts
+/// playing, for the songs that were found without them, such as the ones in
+/// playlists.
+pub struct ChapterLoader {
+    pub player: Player,
+    pub video_id: String,
+}
+
+#[async_trait]
+impl EventHandler for ChapterLoader {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        let EventContext::Track(track_list) = ctx else {
+            return None;
+        };
+
+        for (_, handle) in track_list.iter() {
+            // fetching could hold up the other events of the call
+            tokio::spawn(load_chapters(
+                self.player.clone(),
+                (*handle).clone(),
+                self.video_id.clone(),
+            ));
+        }
+
+        // the chapters are only fetched the first time the track plays
+        Some(Event::Cancel)
+    }
+}
+
+async fn load_chapters(player: Player, track: TrackHandle, video_id: String) {
+    let client = PipedClient::new(&player.client, &player.piped);
+    let streams = match client.streams(&video_id).await {
+        Ok(streams) => streams,
+        Err(cause) => {
+            error!(%cause, video_id, "failed to fetch the chapters");
+            return;
+        }
+    };
+
+    let chapters = streams
+        .chapters
+        .into_iter()
+        .map(|chapter| Chapter {
+            title: chapter.title,
+            start: Duration::from_secs(chapter.start),
+        })
+        .collect::<Vec<_>>();
+
+    {
+        let mut map = track.typemap().write().await;
+        if let Some(meta) = map.get_mut::<SongMetadataKey>() {
+            meta.chapters = Some(chapters.clone());
+        }
+    }
+
+    if !chapters.is_empty() {
+        info!(video_id, count = chapters.len(), "found chapters");
+        player.announce_chapters(&track, chapters);
+    }
+}
+
+/// The chapter `/chapter` jumped to, which isn't announced since the command
+/// already was answered
+pub struct ChapterJumpKey;
+
+impl TypeMapKey for ChapterJumpKey {
+    type Value = usize;
+}
+
+/// Lets the channel know whenever the track moves on to another chapter.
+pub struct ChapterNotifier {
+    pub player: Player,
+    pub chapters: Vec<Chapter>,
+    /// The chapter that was playing on the last check
+    pub current: Mutex<Option<usize>>,
+}
+
+#[async_trait]
+impl EventHandler for ChapterNotifier {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        let EventContext::Track(track_list) = ctx else {
+            return None;
+        };
+
+        for (state, handle) in track_list.iter() {
+            if state.playing != PlayMode::Play {
+                continue;
+            }
+
+            let Some(index) = self
+                .chapters
+                .iter()
+                .rposition(|chapter| chapter.start <= state.position)
+            else {
+                continue;
+            };
+            let previous = self
+                .current
+                .lock()
+                .expect("current chapter lock poisoned")
+                .replace(index);
+            // the chapter the track starts in isn't announced, since the
+            // song itself already was
+            if previous.is_none() || previous == Some(index) {
+                continue;
+            }
+            let jumped = handle.typemap().write().await.remove::<ChapterJumpKey>();
+            if jumped == Some(index) {
+                continue;
+            }
+
+            let locale = self.player.locale;
+            let message = t!(
+                locale,
+                "chapter.now_playing",
+                number = index + 1,
+                total = self.chapters.len(),
+                title = self.chapters[index].title
+            );
+            if let Err(cause) = self
+                .player
+                .channel_id
+                .send_message(&self.player.http, CreateMessage::new().content(message))
+                .await
+            {
+                error!(%cause, "failed to send message")
+            }
+        }
+
+        None
+    }
+}
@@ -1 +1,5 @@
+pub mod autoplay;
+pub mod chapters;
+pub mod sponsorblock;
 pub mod track;
+pub mod voice;
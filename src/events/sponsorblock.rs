@@ -0,0 +1,158 @@
+use std::{sync::Mutex, time::Duration};
+
+use serenity::{async_trait, builder::CreateMessage};
+use songbird::{
+    tracks::{PlayMode, TrackHandle},
+    Event, EventContext, EventHandler,
+};
+use tracing::{debug, error, info};
+
+use crate::{guild::SponsorCategory, i18n::t, player::Player, tools::piped::PipedClient};
+
+/// How often the position of the track is checked against its segments
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Fetches the SponsorBlock segments of a YouTube video once its track starts
+/// playing, then lets a [`SegmentSkipper`] watch the track.
+pub struct SegmentLoader {
+    pub player: Player,
+    pub video_id: String,
+}
+
+#[async_trait]
+impl EventHandler for SegmentLoader {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        let EventContext::Track(track_list) = ctx else {
+            return None;
+        };
+
+        for (_, handle) in track_list.iter() {
+            // fetching could hold up the other events of the call
+            tokio::spawn(load_segments(
+                self.player.clone(),
+                (*handle).clone(),
+                self.video_id.clone(),
+            ));
+        }
+
+        // the segments are only fetched the first time the track plays
+        Some(Event::Cancel)
+    }
+}
+
+async fn load_segments(player: Player, track: TrackHandle, video_id: String) {
+    let categories = SponsorCategory::ALL.map(SponsorCategory::id);
+    let client = PipedClient::new(&player.client, &player.piped);
+    let sponsors = match client.sponsors(&video_id, &categories).await {
+        Ok(sponsors) => sponsors,
+        // videos without segments are answered with an error
+        Err(cause) => {
+            debug!(%cause, video_id, "no sponsorblock segments");
+            return;
+        }
+    };
+
+    let segments = sponsors
+        .segments
+        .iter()
+        .filter(|segment| segment.is_skippable() && segment.end() > segment.start())
+        .filter_map(|segment| {
+            let category = SponsorCategory::from_id(&segment.category)?;
+            Some(Segment {
+                category,
+                start: segment.start(),
+                end: segment.end(),
+            })
+        })
+        .collect::<Vec<_>>();
+    if segments.is_empty() {
+        return;
+    }
+
+    info!(
+        video_id,
+        count = segments.len(),
+        "found sponsorblock segments"
+    );
+    let skipper = SegmentSkipper {
+        player,
+        segments,
+        skipping: Mutex::new(None),
+    };
+    if let Err(cause) = track.add_event(Event::Periodic(CHECK_INTERVAL, None), skipper) {
+        error!(%cause, "failed to create sponsorblock event")
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    category: SponsorCategory,
+    start: Duration,
+    end: Duration,
+}
+
+/// Seeks past the segments of the track in the categories the guild chose to
+/// skip, and lets the channel know.
+pub struct SegmentSkipper {
+    player: Player,
+    segments: Vec<Segment>,
+    /// The segment being skipped, since seeking takes a while to finish
+    skipping: Mutex<Option<usize>>,
+}
+
+#[async_trait]
+impl EventHandler for SegmentSkipper {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        let EventContext::Track(track_list) = ctx else {
+            return None;
+        };
+        let settings = self.player.settings.get(self.player.guild_id).await;
+
+        for (state, handle) in track_list.iter() {
+            if state.playing != PlayMode::Play {
+                continue;
+            }
+
+            let current = self.segments.iter().position(|segment| {
+                (segment.start..segment.end).contains(&state.position)
+                    && settings.sponsor_categories.contains(&segment.category)
+            });
+            let Some(index) = current else {
+                *self.skipping.lock().expect("skipping lock poisoned") = None;
+                continue;
+            };
+            if self
+                .skipping
+                .lock()
+                .expect("skipping lock poisoned")
+                .replace(index)
+                == Some(index)
+            {
+                continue;
+            }
+
+            let segment = self.segments[index];
+            info!(category = segment.category.id(), end = ?segment.end, "skipping segment");
+            // the result only tells whether the seek worked once it's done
+            let _ = handle.seek(segment.end);
+
+            let locale = self.player.locale;
+            let message = t!(
+                locale,
+                "sponsorblock.skipped",
+                category = segment.category.label(locale),
+                seconds = (segment.end - segment.start).as_secs()
+            );
+            if let Err(cause) = self
+                .player
+                .channel_id
+                .send_message(&self.player.http, CreateMessage::new().content(message))
+                .await
+            {
+                error!(%cause, "failed to send message")
+            }
+        }
+
+        None
+    }
+}
@@ -1,24 +1,27 @@
 use std::sync::Arc;
 
 use serenity::{
-    all::ChannelId,
     async_trait,
-    builder::{CreateEmbed, CreateMessage},
-    http::Http,
+    builder::{CreateAttachment, CreateEmbed, CreateMessage},
     model::Colour,
-    prelude::Context,
 };
-use songbird::{Event, EventContext, EventHandler};
+use songbird::{tracks::PlayMode, Event, EventContext, EventHandler};
 use tracing::{error, info};
 
+use crate::{
+    guild::LoopMode,
+    i18n::t,
+    player::{Player, SongMetadataKey},
+    tools::tags::Cover,
+};
+
 pub struct PlayingSongNotifier {
-    pub channel_id: ChannelId,
-    pub http: Arc<Http>,
-    pub context: Context,
+    pub player: Player,
 
     pub title: String,
     pub username: String,
     pub thumbnail: Option<String>,
+    pub cover: Option<Arc<Cover>>,
 }
 
 #[async_trait]
@@ -30,18 +33,36 @@ impl EventHandler for PlayingSongNotifier {
                 track_list.len()
             );
 
+            let locale = self.player.locale;
             let mut embed = CreateEmbed::new()
                 .color(Colour::BLUE)
-                .field("TOCANDO AGORA", self.title.clone(), true)
-                .field("", format!("requisitado por **{}**", self.username), false);
+                .field(t!(locale, "player.now_playing"), self.title.clone(), true)
+                .field(
+                    "",
+                    t!(locale, "player.requested_by", user = self.username),
+                    false,
+                );
+
+            let settings = self.player.settings.get(self.player.guild_id).await;
+            if let Some(label) = settings.loop_mode.label(locale) {
+                embed = embed.field("", label, false);
+            }
 
-            if let Some(thumb) = self.thumbnail.clone() {
+            let mut message = CreateMessage::new();
+            if let Some(cover) = &self.cover {
+                // uploaded with the message, since the links to attachments
+                // expire after a while
+                let name = format!("cover.{}", cover.extension());
+                embed = embed.thumbnail(format!("attachment://{name}"));
+                message = message.add_file(CreateAttachment::bytes(cover.data.clone(), name));
+            } else if let Some(thumb) = self.thumbnail.clone() {
                 embed = embed.thumbnail(thumb);
             }
 
             if let Err(cause) = self
+                .player
                 .channel_id
-                .send_message(&self.http, CreateMessage::new().add_embed(embed))
+                .send_message(&self.player.http, message.add_embed(embed))
                 .await
             {
                 error!(%cause, "failed to send message")
@@ -51,3 +72,87 @@ impl EventHandler for PlayingSongNotifier {
         None
     }
 }
+
+/// Applies the guild's [`LoopMode`] to the track when it starts, and puts it
+/// back at the end of the queue once it finishes in queue mode.
+pub struct TrackLooper {
+    pub player: Player,
+}
+
+#[async_trait]
+impl EventHandler for TrackLooper {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        let EventContext::Track(track_list) = ctx else {
+            return None;
+        };
+        let settings = self.player.settings.get(self.player.guild_id).await;
+
+        for (state, handle) in track_list.iter() {
+            match state.playing {
+                PlayMode::Play => {
+                    let result = if settings.loop_mode == LoopMode::Track {
+                        handle.enable_loop()
+                    } else {
+                        handle.disable_loop()
+                    };
+                    if let Err(cause) = result {
+                        error!(%cause, "failed to set the track loop");
+                    }
+                }
+                // tracks that were stopped by hand (e.g. skipped) are left out
+                PlayMode::End if settings.loop_mode == LoopMode::Queue => {
+                    let meta = {
+                        let map = handle.typemap().read().await;
+                        map.get::<SongMetadataKey>().cloned()
+                    };
+                    let Some(meta) = meta else {
+                        continue;
+                    };
+
+                    info!(title = meta.title, "re-enqueueing looped track");
+                    self.player.enqueue(self.player.source(&meta), meta).await;
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
+}
+
+/// Records the tracks in the guild's history as they start, and whether they
+/// were skipped once they end.
+pub struct HistoryRecorder {
+    pub player: Player,
+}
+
+#[async_trait]
+impl EventHandler for HistoryRecorder {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        let EventContext::Track(track_list) = ctx else {
+            return None;
+        };
+        let history = &self.player.history;
+        let guild_id = self.player.guild_id;
+
+        for (state, handle) in track_list.iter() {
+            let track_id = handle.uuid().as_u128();
+            match state.playing {
+                PlayMode::Play => {
+                    let meta = {
+                        let map = handle.typemap().read().await;
+                        map.get::<SongMetadataKey>().cloned()
+                    };
+                    if let Some(meta) = meta {
+                        history.record(guild_id, track_id, meta).await;
+                    }
+                }
+                // only the tracks that were stopped by hand end this way
+                PlayMode::Stop => history.mark_skipped(guild_id, track_id).await,
+                _ => {}
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,147 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serenity::{
+    all::{GuildId, VoiceState},
+    async_trait,
+    cache::Cache,
+    http::Http,
+    prelude::Context,
+};
+use songbird::{Event, EventContext, EventHandler, Songbird};
+use tracing::{error, info};
+
+use crate::{
+    guild::{GuildSettingsKey, GuildSettingsStore},
+    i18n::{self, Locale},
+};
+
+/// Leaves the voice channel once the queue has been empty for `timeout`.
+pub struct IdleLeaver {
+    pub guild_id: GuildId,
+    pub manager: Arc<Songbird>,
+    pub cache: Arc<Cache>,
+    pub http: Arc<Http>,
+    pub settings: GuildSettingsStore,
+    pub timeout: Duration,
+
+    pub idle_since: Mutex<Option<Instant>>,
+}
+
+#[async_trait]
+impl EventHandler for IdleLeaver {
+    async fn act(&self, _ctx: &EventContext<'_>) -> Option<Event> {
+        let handler_lock = self.manager.get(self.guild_id)?;
+        let is_idle = handler_lock.lock().await.queue().is_empty();
+
+        let idle_for = {
+            let mut idle_since = self.idle_since.lock().expect("poisoned idle lock");
+            if !is_idle {
+                *idle_since = None;
+                return None;
+            }
+            idle_since.get_or_insert_with(Instant::now).elapsed()
+        };
+
+        if idle_for >= self.timeout {
+            info!(guild_id = self.guild_id.get(), "leaving idle channel");
+            leave_channel(
+                &self.manager,
+                &self.cache,
+                &self.http,
+                &self.settings,
+                self.guild_id,
+                "leave.idle",
+            )
+            .await;
+        }
+
+        None
+    }
+}
+
+/// Leaves the voice channel of the guild if the bot was left alone in it.
+pub async fn leave_if_alone(ctx: &Context, state: &VoiceState) {
+    let Some(guild_id) = state.guild_id else {
+        return;
+    };
+    let bot_id = ctx.cache.current_user().id;
+
+    let is_alone = {
+        let Some(guild) = ctx.cache.guild(guild_id) else {
+            return;
+        };
+        let Some(channel_id) = guild
+            .voice_states
+            .get(&bot_id)
+            .and_then(|state| state.channel_id)
+        else {
+            return;
+        };
+
+        !guild
+            .voice_states
+            .values()
+            .any(|state| state.channel_id == Some(channel_id) && state.user_id != bot_id)
+    };
+
+    if !is_alone {
+        return;
+    }
+
+    let Some(manager) = songbird::get(ctx).await else {
+        return;
+    };
+    let settings = {
+        let data = ctx.data.read().await;
+        data.get::<GuildSettingsKey>()
+            .expect("guild settings not found")
+            .clone()
+    };
+
+    info!(guild_id = guild_id.get(), "leaving empty channel");
+    leave_channel(
+        &manager,
+        &ctx.cache,
+        &ctx.http,
+        &settings,
+        guild_id,
+        "leave.alone",
+    )
+    .await;
+}
+
+/// Disconnects from the guild's voice channel, telling why in the channel
+/// that last used `/play`.
+async fn leave_channel(
+    manager: &Songbird,
+    cache: &Cache,
+    http: &Http,
+    settings: &GuildSettingsStore,
+    guild_id: GuildId,
+    message_key: &'static str,
+) {
+    if let Err(cause) = manager.remove(guild_id).await {
+        error!(%cause, "failed to leave channel");
+        return;
+    }
+
+    let settings = settings.get(guild_id).await;
+    let Some(channel_id) = settings.last_channel else {
+        return;
+    };
+
+    // there's no user to take the language from, so the guild's is used
+    let locale = settings.locale.unwrap_or_else(|| {
+        cache
+            .guild(guild_id)
+            .map(|guild| Locale::from_code(&guild.preferred_locale))
+            .unwrap_or_default()
+    });
+
+    if let Err(cause) = channel_id.say(http, i18n::tr(locale, message_key)).await {
+        error!(%cause, "failed to send message")
+    }
+}
@@ -0,0 +1,159 @@
+use std::{collections::HashMap, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::{ChannelId, GuildId},
+    prelude::TypeMapKey,
+};
+use tokio::sync::RwLock;
+
+use crate::i18n::{t, Locale};
+
+/// How the queue behaves when a track ends
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, poise::ChoiceParameter,
+)]
+pub enum LoopMode {
+    /// Loops the current track
+    #[name = "track"]
+    #[name_localized("pt-BR", "música")]
+    Track,
+    /// Puts each track at the end of the queue once it ends
+    #[name = "queue"]
+    #[name_localized("pt-BR", "fila")]
+    Queue,
+    /// Doesn't loop anything
+    #[default]
+    #[name = "off"]
+    #[name_localized("pt-BR", "desligado")]
+    Off,
+}
+
+impl LoopMode {
+    /// A short description of the mode, or `None` when nothing is looping.
+    pub fn label(self, locale: Locale) -> Option<String> {
+        match self {
+            LoopMode::Track => Some(t!(locale, "loop.track")),
+            LoopMode::Queue => Some(t!(locale, "loop.queue")),
+            LoopMode::Off => None,
+        }
+    }
+}
+
+/// The kinds of SponsorBlock segments that can be skipped
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, poise::ChoiceParameter,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SponsorCategory {
+    /// Paid promotions and self promotion
+    #[name = "sponsor"]
+    #[name_localized("pt-BR", "patrocínio")]
+    Sponsor,
+    #[name = "intro"]
+    #[name_localized("pt-BR", "introdução")]
+    Intro,
+    /// Endcards and credits
+    #[name = "outro"]
+    #[name_localized("pt-BR", "encerramento")]
+    Outro,
+    /// The parts of music videos without music
+    #[name = "non-music"]
+    #[name_localized("pt-BR", "sem música")]
+    MusicOfftopic,
+}
+
+impl SponsorCategory {
+    pub const ALL: [SponsorCategory; 4] = [
+        SponsorCategory::Sponsor,
+        SponsorCategory::Intro,
+        SponsorCategory::Outro,
+        SponsorCategory::MusicOfftopic,
+    ];
+
+    /// The name of the category in the SponsorBlock API.
+    pub fn id(self) -> &'static str {
+        match self {
+            SponsorCategory::Sponsor => "sponsor",
+            SponsorCategory::Intro => "intro",
+            SponsorCategory::Outro => "outro",
+            SponsorCategory::MusicOfftopic => "music_offtopic",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|category| category.id() == id)
+    }
+
+    pub fn label(self, locale: Locale) -> String {
+        match self {
+            SponsorCategory::Sponsor => t!(locale, "sponsorblock.sponsor"),
+            SponsorCategory::Intro => t!(locale, "sponsorblock.intro"),
+            SponsorCategory::Outro => t!(locale, "sponsorblock.outro"),
+            SponsorCategory::MusicOfftopic => t!(locale, "sponsorblock.music_offtopic"),
+        }
+    }
+}
+
+/// Playback settings that outlive the tracks of a guild
+#[derive(Debug, Clone)]
+pub struct GuildSettings {
+    pub loop_mode: LoopMode,
+    /// The volume of the tracks, in percent
+    pub volume: u16,
+    /// The channel that last used `/play`, where the bot posts its notices
+    pub last_channel: Option<ChannelId>,
+    /// The language chosen for the guild, instead of each user's
+    pub locale: Option<Locale>,
+    /// Whether related songs are played when the queue runs out
+    pub autoplay: bool,
+    /// The SponsorBlock segments that are skipped in YouTube videos
+    pub sponsor_categories: Vec<SponsorCategory>,
+}
+
+impl GuildSettings {
+    /// The volume in the scale used by songbird, where `1.0` is unchanged.
+    pub fn track_volume(&self) -> f32 {
+        f32::from(self.volume) / 100.0
+    }
+}
+
+impl Default for GuildSettings {
+    fn default() -> Self {
+        Self {
+            loop_mode: LoopMode::default(),
+            volume: 100,
+            last_channel: None,
+            locale: None,
+            autoplay: false,
+            // skipping the rest is up to each guild
+            sponsor_categories: vec![SponsorCategory::Sponsor],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GuildSettingsStore {
+    inner: Arc<RwLock<HashMap<GuildId, GuildSettings>>>,
+}
+
+impl GuildSettingsStore {
+    pub async fn get(&self, guild_id: GuildId) -> GuildSettings {
+        let map = self.inner.read().await;
+        map.get(&guild_id).cloned().unwrap_or_default()
+    }
+
+    pub async fn update<F, O>(&self, guild_id: GuildId, func: F) -> O
+    where
+        F: FnOnce(&mut GuildSettings) -> O,
+    {
+        let mut map = self.inner.write().await;
+        func(map.entry(guild_id).or_default())
+    }
+}
+
+pub struct GuildSettingsKey;
+
+impl TypeMapKey for GuildSettingsKey {
+    type Value = GuildSettingsStore;
+}
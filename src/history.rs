@@ -0,0 +1,83 @@
+//! The tracks that were played in each guild, most recent first.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+
+use serenity::{
+    all::{GuildId, Timestamp},
+    prelude::TypeMapKey,
+};
+use tokio::sync::RwLock;
+
+use crate::player::SongMetadata;
+
+/// How many tracks are remembered in each guild
+const MAX_HISTORY_SIZE: usize = 100;
+
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub song: SongMetadata,
+    pub started_at: Timestamp,
+    /// Whether the track was stopped before its end
+    pub skipped: bool,
+    /// The id of the track that played the song, to find the entry once it
+    /// ends
+    track_id: u128,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HistoryStore {
+    inner: Arc<RwLock<HashMap<GuildId, VecDeque<HistoryEntry>>>>,
+}
+
+impl HistoryStore {
+    /// Records that the track `track_id` started playing `song`, forgetting
+    /// the oldest entry if the history is full.
+    pub async fn record(&self, guild_id: GuildId, track_id: u128, song: SongMetadata) {
+        let mut map = self.inner.write().await;
+        let history = map.entry(guild_id).or_default();
+
+        // the track also starts playing again when it's resumed
+        if history
+            .front()
+            .is_some_and(|entry| entry.track_id == track_id)
+        {
+            return;
+        }
+
+        history.push_front(HistoryEntry {
+            song,
+            started_at: Timestamp::now(),
+            skipped: false,
+            track_id,
+        });
+        history.truncate(MAX_HISTORY_SIZE);
+    }
+
+    /// Marks the last entry of the track `track_id` as skipped.
+    pub async fn mark_skipped(&self, guild_id: GuildId, track_id: u128) {
+        let mut map = self.inner.write().await;
+        let entry = map
+            .get_mut(&guild_id)
+            .and_then(|history| history.iter_mut().find(|entry| entry.track_id == track_id));
+        if let Some(entry) = entry {
+            entry.skipped = true;
+        }
+    }
+
+    /// The guild's history, most recent first.
+    pub async fn get(&self, guild_id: GuildId) -> Vec<HistoryEntry> {
+        let map = self.inner.read().await;
+        map.get(&guild_id)
+            .map(|history| history.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+pub struct HistoryKey;
+
+impl TypeMapKey for HistoryKey {
+    type Value = HistoryStore;
+}
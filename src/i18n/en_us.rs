@@ -0,0 +1,168 @@
+pub(super) const MESSAGES: &[(&str, &str)] = &[
+    ("error.not_in_guild", "This command only works in servers"),
+    (
+        "error.not_in_voice",
+        "You must join a voice channel before using this command",
+    ),
+    ("error.not_connected", "I'm not in any channel"),
+    (
+        "error.join_failed",
+        "I couldn't join the channel. Try again later",
+    ),
+    ("error.leave_failed", "I couldn't leave the channel"),
+    ("error.invalid_query", "Invalid song URL or name"),
+    ("error.resolve_failed", "I couldn't find a song at that URL"),
+    ("error.not_audio_file", "I can't play that file"),
+    ("error.search_failed", "I couldn't search for any songs"),
+    ("error.no_results", "I couldn't find any songs"),
+    ("error.playlist_failed", "I couldn't load the playlist"),
+    ("error.empty_playlist", "The playlist is empty"),
+    ("error.empty_queue", "The queue is empty"),
+    ("error.nothing_playing", "Nothing is playing right now"),
+    (
+        "error.not_enough_tracks",
+        "There aren't enough songs to shuffle",
+    ),
+    ("error.invalid_range", "Invalid range"),
+    ("error.invalid_position", "Invalid position: {position}"),
+    (
+        "error.current_track_playing",
+        "The song at position 1 is playing. Use the `force` option to change it anyway",
+    ),
+    ("error.pause_failed", "I couldn't pause"),
+    ("error.resume_failed", "I couldn't resume"),
+    (
+        "error.loop_failed",
+        "I couldn't change the current song's loop",
+    ),
+    (
+        "error.invalid_timestamp",
+        "Invalid format. Use something like `1:23`, `83`, `+30s` or `-15s`",
+    ),
+    ("error.not_seekable", "Live streams can't be seeked"),
+    (
+        "error.seek_out_of_range",
+        "The song is only **{duration}** long",
+    ),
+    (
+        "error.seek_failed",
+        "I couldn't seek to that point of the song",
+    ),
+    ("error.no_session", "There's no session to resume"),
+    (
+        "error.queue_not_empty",
+        "The queue must be empty to resume the session",
+    ),
+    ("error.playlist_not_found", "There's no playlist named **{name}**"),
+    (
+        "error.playlist_not_owned",
+        "Only whoever saved **{name}** or who can manage the server can change it",
+    ),
+    ("error.empty_playlist_name", "The playlist name can't be empty"),
+    ("error.empty_history", "No songs were played yet"),
+    ("error.no_chapters", "The current song has no chapters"),
+    (
+        "error.invalid_chapter",
+        "Invalid chapter: use next, prev or a number from /chapters",
+    ),
+    ("error.unknown", "Something went wrong :sob:"),
+    ("queue.header", "CURRENT QUEUE"),
+    ("queue.more", "... and {count} more"),
+    ("loop.track", "🔂 looping the song"),
+    ("loop.queue", "🔁 looping the queue"),
+    ("loop.off", "Loop disabled"),
+    ("player.now_playing", "NOW PLAYING"),
+    ("player.requested_by", "requested by **{user}**"),
+    ("player.live", "live"),
+    ("nowplaying.pause", "⏸️ Pause"),
+    ("nowplaying.resume", "▶️ Resume"),
+    ("nowplaying.skip", "⏭️ Skip"),
+    ("nowplaying.loop_off", "🔁 Loop"),
+    ("nowplaying.loop_track", "🔂 Song"),
+    ("nowplaying.loop_queue", "🔁 Queue"),
+    ("join.joining", "Joining **{channel}**"),
+    ("leave.leaving", "Leaving the channel"),
+    ("leave.idle", "Leaving the channel due to inactivity"),
+    ("leave.alone", "Leaving the channel because everyone left"),
+    ("pause.paused", "Song paused"),
+    ("pause.resumed", "Song resumed"),
+    ("play.adding", "Adding ||{title}|| to the queue"),
+    ("play.results", "Results"),
+    ("play.views", "{views} views"),
+    ("play.short", "short"),
+    ("picker.placeholder", "Choose the songs"),
+    ("picker.previous", "◀️ Previous"),
+    ("picker.next", "Next ▶️"),
+    ("picker.cancel", "Cancel"),
+    ("picker.page", "Page {page}"),
+    (
+        "picker.not_yours",
+        "Only the person who searched can choose the songs",
+    ),
+    (
+        "play.adding_playlist",
+        "Adding **{count}** songs from the playlist ||{name}|| to the queue",
+    ),
+    ("play.playlist_limited", " (limited to {limit})"),
+    ("remove.removing", "Removing **{title}** from the queue"),
+    ("move.moving", "Moving **{title}** to position {position}"),
+    ("swap.swapping", "Swapping **{a}** and **{b}**"),
+    (
+        "clear.removing",
+        "Removing **{count}** song(s) from the queue:\n{titles}",
+    ),
+    ("skip.skipping", "Skipping **{count}** song(s):\n{titles}"),
+    ("seek.seeking", "Seeking to **{position}**"),
+    ("shuffle.shuffling", "Shuffling **{count}** song(s)"),
+    ("volume.set", "Volume at **{volume}%**"),
+    ("language.set", "I'll now speak **{language}**"),
+    (
+        "language.auto",
+        "I'll now speak each person's Discord language",
+    ),
+    (
+        "session.offer",
+        "I was restarted with {count} songs in the queue. Use `/resume-session` to continue where you left off",
+    ),
+    ("session.resumed", "Resuming the session with {count} songs"),
+    ("playlist.saved", "Saved the playlist **{name}** with {count} songs"),
+    (
+        "playlist.loading",
+        "Adding the playlist **{name}** to the queue ({count} songs)",
+    ),
+    ("playlist.skipped", " ({count} songs couldn't be found anymore)"),
+    (
+        "playlist.added",
+        "Added ||{title}|| to the playlist **{name}**, which now has {count} songs",
+    ),
+    ("playlist.deleted", "Deleted the playlist **{name}**"),
+    ("playlist.personal", "Your playlists"),
+    ("playlist.guild", "Server playlists"),
+    ("playlist.entry", "**{name}** ({count} songs)"),
+    ("playlist.none", "No playlists saved yet"),
+    ("history.header", "HISTORY ({page}/{pages})"),
+    ("history.skipped", "skipped"),
+    (
+        "autoplay.on",
+        "Autoplay is on: I'll play related songs when the queue runs out",
+    ),
+    ("autoplay.off", "Autoplay is off"),
+    ("autoplay.requester", "Autoplay"),
+    ("autoplay.label", "autoplay"),
+    ("sponsorblock.sponsor", "sponsor"),
+    ("sponsorblock.intro", "intro"),
+    ("sponsorblock.outro", "outro"),
+    ("sponsorblock.music_offtopic", "non-music section"),
+    ("sponsorblock.skipping", "Skipping these segments: {categories}"),
+    ("sponsorblock.off", "No segments will be skipped"),
+    ("sponsorblock.skipped", "⏭️ Skipped a segment: {category} ({seconds}s)"),
+    ("chapters.header", "CHAPTERS OF {title} ({page}/{pages})"),
+    (
+        "chapter.seeking",
+        "Jumping to chapter {number}: **{title}** ({position})",
+    ),
+    (
+        "chapter.now_playing",
+        "📖 Chapter {number}/{total}: **{title}**",
+    ),
+];
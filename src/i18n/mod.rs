@@ -0,0 +1,94 @@
+//! Keyed message catalogs for the languages the bot speaks.
+//!
+//! Messages are looked up with the [`t!`] macro, which replaces `{name}`
+//! placeholders in the message with the given arguments:
+//!
+//! ```ignore
+//! t!(locale, "volume.set", volume = 50)
+//! ```
+
+use std::{collections::HashMap, fmt::Display};
+
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+mod en_us;
+mod pt_br;
+
+/// A language with a message catalog
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, poise::ChoiceParameter,
+)]
+pub enum Locale {
+    #[default]
+    #[name = "Português (Brasil)"]
+    PtBr,
+    #[name = "English (US)"]
+    EnUs,
+}
+
+impl Locale {
+    /// Picks the catalog for a Discord locale code, such as `pt-BR` or
+    /// `en-GB`, falling back to English.
+    pub fn from_code(code: &str) -> Self {
+        if code.starts_with("pt") {
+            Locale::PtBr
+        } else {
+            Locale::EnUs
+        }
+    }
+
+    fn messages(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::PtBr => pt_br::MESSAGES,
+            Locale::EnUs => en_us::MESSAGES,
+        }
+    }
+}
+
+lazy_static! {
+    static ref CATALOGS: HashMap<Locale, HashMap<&'static str, &'static str>> =
+        [Locale::PtBr, Locale::EnUs]
+            .into_iter()
+            .map(|locale| (locale, locale.messages().iter().copied().collect()))
+            .collect();
+}
+
+/// Looks up the message for `key`, falling back to the default locale and
+/// then to the key itself.
+pub fn tr(locale: Locale, key: &'static str) -> &'static str {
+    [locale, Locale::default()]
+        .into_iter()
+        .find_map(|locale| CATALOGS[&locale].get(key).copied())
+        .unwrap_or_else(|| {
+            warn!(key, "missing translation");
+            key
+        })
+}
+
+/// Replaces the `{name}` placeholders of `template` with `args`.
+///
+/// The arguments are `Sync` so that messages can be built in the middle of the
+/// `await`s of a command.
+pub fn format(template: &str, args: &[(&str, &(dyn Display + Sync))]) -> String {
+    args.iter()
+        .fold(template.to_string(), |message, (name, value)| {
+            message.replace(&format!("{{{name}}}"), &value.to_string())
+        })
+}
+
+/// Translates a message key, optionally filling its placeholders.
+macro_rules! t {
+    ($locale:expr, $key:literal $(,)?) => {
+        $crate::i18n::tr($locale, $key).to_string()
+    };
+    ($locale:expr, $key:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::format(
+            $crate::i18n::tr($locale, $key),
+            &[$((stringify!($name), &$value as &(dyn ::std::fmt::Display + Sync))),+],
+        )
+    };
+}
+
+pub(crate) use t;
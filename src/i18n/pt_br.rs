@@ -0,0 +1,186 @@
+pub(super) const MESSAGES: &[(&str, &str)] = &[
+    (
+        "error.not_in_guild",
+        "Esse comando só funciona em servidores",
+    ),
+    (
+        "error.not_in_voice",
+        "Você deve entrar em um canal de voz antes de usar esse comando",
+    ),
+    ("error.not_connected", "Não estou em nenhum canal"),
+    (
+        "error.join_failed",
+        "Não consegui entrar no canal. Tente novamente mais tarde",
+    ),
+    ("error.leave_failed", "Não consegui sair do canal"),
+    ("error.invalid_query", "URL ou nome da música inválidos"),
+    (
+        "error.resolve_failed",
+        "Não encontrei nenhuma música nessa URL",
+    ),
+    ("error.not_audio_file", "Não consigo tocar esse arquivo"),
+    (
+        "error.search_failed",
+        "Não consegui pesquisar nenhuma música",
+    ),
+    ("error.no_results", "Não encontrei nenhuma música"),
+    ("error.playlist_failed", "Não consegui carregar a playlist"),
+    ("error.empty_playlist", "A playlist está vazia"),
+    ("error.empty_queue", "A lista está vazia"),
+    ("error.nothing_playing", "Nada está tocando agora"),
+    (
+        "error.not_enough_tracks",
+        "Não há músicas suficientes para embaralhar",
+    ),
+    ("error.invalid_range", "Intervalo inválido"),
+    ("error.invalid_position", "Posição inválida: {position}"),
+    (
+        "error.current_track_playing",
+        "A música na posição 1 está tocando. Use a opção `force` para alterá-la mesmo assim",
+    ),
+    ("error.pause_failed", "Não consegui pausar"),
+    ("error.resume_failed", "Não consegui despausar"),
+    (
+        "error.loop_failed",
+        "Não consegui mudar a repetição da música atual",
+    ),
+    (
+        "error.invalid_timestamp",
+        "Formato inválido. Use algo como `1:23`, `83`, `+30s` ou `-15s`",
+    ),
+    (
+        "error.not_seekable",
+        "Não dá para pular em transmissões ao vivo",
+    ),
+    ("error.seek_out_of_range", "A música só tem **{duration}**"),
+    (
+        "error.seek_failed",
+        "Não consegui pular para esse ponto da música",
+    ),
+    ("error.no_session", "Não há nenhuma sessão para retomar"),
+    (
+        "error.queue_not_empty",
+        "A fila deve estar vazia para retomar a sessão",
+    ),
+    (
+        "error.playlist_not_found",
+        "Não existe nenhuma playlist chamada **{name}**",
+    ),
+    (
+        "error.playlist_not_owned",
+        "Só quem salvou **{name}** ou quem pode gerenciar o servidor pode mudá-la",
+    ),
+    ("error.empty_playlist_name", "O nome da playlist não pode ficar vazio"),
+    ("error.empty_history", "Nenhuma música tocou ainda"),
+    ("error.no_chapters", "A música atual não tem capítulos"),
+    (
+        "error.invalid_chapter",
+        "Capítulo inválido: use próximo, anterior ou um número de /chapters",
+    ),
+    ("error.unknown", "Deu ruim :sob:"),
+    ("queue.header", "LISTA DE REPRODUÇÃO ATUAL"),
+    ("queue.more", "... e mais {count}"),
+    ("loop.track", "🔂 repetindo a música"),
+    ("loop.queue", "🔁 repetindo a fila"),
+    ("loop.off", "Repetição desativada"),
+    ("player.now_playing", "TOCANDO AGORA"),
+    ("player.requested_by", "requisitado por **{user}**"),
+    ("player.live", "ao vivo"),
+    ("nowplaying.pause", "⏸️ Pausar"),
+    ("nowplaying.resume", "▶️ Continuar"),
+    ("nowplaying.skip", "⏭️ Pular"),
+    ("nowplaying.loop_off", "🔁 Repetir"),
+    ("nowplaying.loop_track", "🔂 Música"),
+    ("nowplaying.loop_queue", "🔁 Fila"),
+    ("join.joining", "Entrando em **{channel}**"),
+    ("leave.leaving", "Saindo do canal"),
+    ("leave.idle", "Saindo do canal por inatividade"),
+    ("leave.alone", "Saindo do canal porque todo mundo saiu"),
+    ("pause.paused", "Música pausada"),
+    ("pause.resumed", "Música despausada"),
+    ("play.adding", "Adicionando ||{title}|| à fila"),
+    ("play.results", "Resultados"),
+    ("play.views", "{views} visualizações"),
+    ("play.short", "short"),
+    ("picker.placeholder", "Escolha as músicas"),
+    ("picker.previous", "◀️ Anterior"),
+    ("picker.next", "Próxima ▶️"),
+    ("picker.cancel", "Cancelar"),
+    ("picker.page", "Página {page}"),
+    (
+        "picker.not_yours",
+        "Só quem pesquisou pode escolher as músicas",
+    ),
+    (
+        "play.adding_playlist",
+        "Adicionando **{count}** músicas da playlist ||{name}|| à fila",
+    ),
+    ("play.playlist_limited", " (limitado a {limit})"),
+    ("remove.removing", "Removendo **{title}** da fila"),
+    (
+        "move.moving",
+        "Movendo **{title}** para a posição {position}",
+    ),
+    ("swap.swapping", "Trocando **{a}** e **{b}** de posição"),
+    (
+        "clear.removing",
+        "Removendo **{count}** música(s) da fila:\n{titles}",
+    ),
+    ("skip.skipping", "Pulando **{count}** música(s):\n{titles}"),
+    ("seek.seeking", "Pulando para **{position}**"),
+    ("shuffle.shuffling", "Embaralhando **{count}** música(s)"),
+    ("volume.set", "Volume em **{volume}%**"),
+    ("language.set", "Agora vou falar em **{language}**"),
+    (
+        "language.auto",
+        "Agora vou falar no idioma do Discord de cada pessoa",
+    ),
+    (
+        "session.offer",
+        "Fui reiniciado com {count} músicas na fila. Use `/resume-session` para continuar de onde parou",
+    ),
+    ("session.resumed", "Retomando a sessão com {count} músicas"),
+    ("playlist.saved", "Playlist **{name}** salva com {count} músicas"),
+    (
+        "playlist.loading",
+        "Adicionando a playlist **{name}** à fila ({count} músicas)",
+    ),
+    (
+        "playlist.skipped",
+        " ({count} músicas não foram mais encontradas)",
+    ),
+    (
+        "playlist.added",
+        "||{title}|| adicionada à playlist **{name}**, que agora tem {count} músicas",
+    ),
+    ("playlist.deleted", "Playlist **{name}** apagada"),
+    ("playlist.personal", "Suas playlists"),
+    ("playlist.guild", "Playlists do servidor"),
+    ("playlist.entry", "**{name}** ({count} músicas)"),
+    ("playlist.none", "Nenhuma playlist salva ainda"),
+    ("history.header", "HISTÓRICO ({page}/{pages})"),
+    ("history.skipped", "pulada"),
+    (
+        "autoplay.on",
+        "Reprodução automática ligada: vou tocar músicas relacionadas quando a fila acabar",
+    ),
+    ("autoplay.off", "Reprodução automática desligada"),
+    ("autoplay.requester", "Reprodução automática"),
+    ("autoplay.label", "automática"),
+    ("sponsorblock.sponsor", "patrocínio"),
+    ("sponsorblock.intro", "introdução"),
+    ("sponsorblock.outro", "encerramento"),
+    ("sponsorblock.music_offtopic", "trecho sem música"),
+    ("sponsorblock.skipping", "Pulando estes trechos: {categories}"),
+    ("sponsorblock.off", "Nenhum trecho será pulado"),
+    ("sponsorblock.skipped", "⏭️ Pulei um trecho: {category} ({seconds}s)"),
+    ("chapters.header", "CAPÍTULOS DE {title} ({page}/{pages})"),
+    (
+        "chapter.seeking",
+        "Pulando para o capítulo {number}: **{title}** ({position})",
+    ),
+    (
+        "chapter.now_playing",
+        "📖 Capítulo {number}/{total}: **{title}**",
+    ),
+];
@@ -1,14 +1,27 @@
 use anyhow::anyhow;
-use commands::{join, leave, list, pause, play};
-// skip};
+use commands::{
+    autoplay, chapter, chapters, clear, history, join, language, leave, list, loop_mode,
+    move_track, nowplaying, pause, play, playfile, playlist, remove, replay, resume_session, seek,
+    shuffle, skip, sponsorblock, swap, volume,
+};
 
+use config::Config;
 use service::CreateService;
 use shuttle_runtime::SecretStore;
 use tracing::info;
 
 mod commands;
+mod config;
 mod events;
+mod guild;
+mod history;
+mod i18n;
+mod player;
+mod playlists;
+mod resolver;
 mod service;
+mod session;
+mod storage;
 mod tools;
 
 #[shuttle_runtime::main]
@@ -36,12 +49,32 @@ async fn serenity(
     }
 
     let service = CreateService::new(token)
+        .with_config(Config::from_secrets(&secrets))
         .with_command(join())
         .with_command(leave())
         .with_command(play())
+        .with_command(playfile())
+        .with_command(playlist())
         .with_command(pause())
-        // .with_command(skip())
+        .with_command(skip())
         .with_command(list())
+        .with_command(history())
+        .with_command(replay())
+        .with_command(nowplaying())
+        .with_command(seek())
+        .with_command(chapters())
+        .with_command(chapter())
+        .with_command(volume())
+        .with_command(remove())
+        .with_command(move_track())
+        .with_command(swap())
+        .with_command(clear())
+        .with_command(shuffle())
+        .with_command(loop_mode())
+        .with_command(autoplay())
+        .with_command(sponsorblock())
+        .with_command(language())
+        .with_command(resume_session())
         .build()
         .await;
     info!("Service created");
@@ -0,0 +1,241 @@
+use std::{sync::Arc, time::Duration};
+
+use reqwest::{Client as HttpClient, Url};
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::{ChannelId, GuildId},
+    http::Http,
+    prelude::TypeMapKey,
+};
+use songbird::{
+    input::{HttpRequest, Input, YoutubeDl},
+    tracks::{Track, TrackHandle},
+    Call, Event, TrackEvent,
+};
+use tokio::sync::Mutex;
+use tracing::error;
+
+use crate::{
+    events::{
+        autoplay::Autoplayer,
+        chapters::{ChapterLoader, ChapterNotifier},
+        sponsorblock::SegmentLoader,
+        track::{HistoryRecorder, PlayingSongNotifier, TrackLooper},
+    },
+    guild::GuildSettingsStore,
+    history::HistoryStore,
+    i18n::Locale,
+    resolver::video_id,
+    tools::{piped::PipedInstances, tags::Cover},
+};
+
+/// How often the position of a track is checked against its chapters
+const CHAPTER_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+pub struct SongMetadataKey;
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SongMetadata {
+    pub title: String,
+    /// The URL the track was created from, used to play it again
+    pub url: String,
+    pub duration: Duration,
+    pub user: String,
+    pub thumbnail: Option<String>,
+    pub source: AudioSource,
+    /// The link the song was requested with, when it's played from another
+    /// one, such as a Spotify track played from YouTube
+    pub origin: Option<String>,
+    /// The channel or artist that published the song
+    #[serde(default)]
+    pub uploader: Option<String>,
+    /// Whether the song was picked by autoplay instead of a user
+    #[serde(default)]
+    pub autoplayed: bool,
+    /// The chapters of the song in order, or `None` if they weren't fetched
+    #[serde(default)]
+    pub chapters: Option<Vec<Chapter>>,
+    /// The cover art embedded in an audio file, attached to the now playing
+    /// message since the file's song has no image to link to
+    #[serde(skip)]
+    pub cover: Option<Arc<Cover>>,
+}
+
+impl SongMetadata {
+    /// The index of the chapter playing at `position`.
+    pub fn chapter_at(&self, position: Duration) -> Option<usize> {
+        let chapters = self.chapters.as_deref()?;
+        chapters
+            .iter()
+            .rposition(|chapter| chapter.start <= position)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Chapter {
+    pub title: String,
+    pub start: Duration,
+}
+
+/// How the audio of a track is fetched from its URL
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AudioSource {
+    /// A page that yt-dlp knows how to extract the audio from
+    #[default]
+    YtDlp,
+    /// An audio file that can be downloaded directly
+    Http,
+}
+
+impl TypeMapKey for SongMetadataKey {
+    type Value = SongMetadata;
+}
+
+/// Everything needed to enqueue tracks in a guild's call and to react to
+/// their events.
+#[derive(Clone)]
+pub struct Player {
+    pub guild_id: GuildId,
+    pub call: Arc<Mutex<Call>>,
+    /// The channel where the track notifications are sent
+    pub channel_id: ChannelId,
+    pub http: Arc<Http>,
+    pub client: HttpClient,
+    pub settings: GuildSettingsStore,
+    /// The language of the track notifications
+    pub locale: Locale,
+    pub history: HistoryStore,
+    pub piped: PipedInstances,
+}
+
+impl Player {
+    /// Creates a new input that plays the track described by `meta` again.
+    pub fn source(&self, meta: &SongMetadata) -> Input {
+        match meta.source {
+            AudioSource::YtDlp => YoutubeDl::new(self.client.clone(), meta.url.clone()).into(),
+            AudioSource::Http => HttpRequest::new(self.client.clone(), meta.url.clone()).into(),
+        }
+    }
+
+    /// Adds `source` to the end of the queue, storing `meta` in the track's
+    /// typemap and registering the track events.
+    pub async fn enqueue(&self, source: Input, meta: SongMetadata) -> TrackHandle {
+        // the known duration is used to preload the next track, so that the
+        // input doesn't need to be queried for its metadata right away
+        let preload_time = (!meta.duration.is_zero())
+            .then(|| meta.duration.saturating_sub(Duration::from_secs(5)));
+        let volume = self.settings.get(self.guild_id).await.track_volume();
+
+        let mut call = self.call.lock().await;
+        let track = call.enqueue_with_preload(Track::from(source).volume(volume), preload_time);
+        // songbird only creates the typemap along with the handle, so the
+        // metadata is stored before the call is unlocked for the commands,
+        // and before the events that read it exist
+        track
+            .typemap()
+            .write()
+            .await
+            .insert::<SongMetadataKey>(meta.clone());
+        drop(call);
+
+        self.register_events(&track, &meta);
+
+        track
+    }
+
+    fn register_events(&self, track: &TrackHandle, meta: &SongMetadata) {
+        if let Err(cause) = track.add_event(
+            Event::Track(TrackEvent::Play),
+            PlayingSongNotifier {
+                player: self.clone(),
+                title: meta.title.clone(),
+                username: meta.user.clone(),
+                thumbnail: meta.thumbnail.clone(),
+                cover: meta.cover.clone(),
+            },
+        ) {
+            error!(%cause, "failed to create song event")
+        }
+
+        for event in [TrackEvent::Play, TrackEvent::End] {
+            if let Err(cause) = track.add_event(
+                Event::Track(event),
+                TrackLooper {
+                    player: self.clone(),
+                },
+            ) {
+                error!(%cause, "failed to create loop event")
+            }
+
+            if let Err(cause) = track.add_event(
+                Event::Track(event),
+                HistoryRecorder {
+                    player: self.clone(),
+                },
+            ) {
+                error!(%cause, "failed to create history event")
+            }
+        }
+
+        if let Err(cause) = track.add_event(
+            Event::Track(TrackEvent::End),
+            Autoplayer {
+                player: self.clone(),
+            },
+        ) {
+            error!(%cause, "failed to create autoplay event")
+        }
+
+        let youtube_id = Url::parse(&meta.url)
+            .ok()
+            .and_then(|url| video_id(&url))
+            .filter(|_| meta.source == AudioSource::YtDlp);
+
+        if let Some(video_id) = youtube_id.clone() {
+            if let Err(cause) = track.add_event(
+                Event::Track(TrackEvent::Play),
+                SegmentLoader {
+                    player: self.clone(),
+                    video_id,
+                },
+            ) {
+                error!(%cause, "failed to create sponsorblock event")
+            }
+        }
+
+        match (&meta.chapters, youtube_id) {
+            (Some(chapters), _) => self.announce_chapters(track, chapters.clone()),
+            (None, Some(video_id)) => {
+                if let Err(cause) = track.add_event(
+                    Event::Track(TrackEvent::Play),
+                    ChapterLoader {
+                        player: self.clone(),
+                        video_id,
+                    },
+                ) {
+                    error!(%cause, "failed to create chapter event")
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    /// Announces the `chapters` of the track as it plays them.
+    pub fn announce_chapters(&self, track: &TrackHandle, chapters: Vec<Chapter>) {
+        if chapters.len() < 2 {
+            return;
+        }
+
+        if let Err(cause) = track.add_event(
+            Event::Periodic(CHAPTER_CHECK_INTERVAL, None),
+            ChapterNotifier {
+                player: self.clone(),
+                chapters,
+                current: Default::default(),
+            },
+        ) {
+            error!(%cause, "failed to create chapter event")
+        }
+    }
+}
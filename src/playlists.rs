@@ -0,0 +1,171 @@
+//! Queues saved by name, to be played again later.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::{GuildId, UserId},
+    prelude::TypeMapKey,
+};
+use tokio::sync::Mutex;
+use tracing::error;
+
+use crate::{
+    player::SongMetadata,
+    storage::{read_json, write_json},
+};
+
+/// Who a playlist belongs to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum PlaylistScope {
+    /// Only the user who saved it can use it
+    #[default]
+    #[name = "personal"]
+    #[name_localized("pt-BR", "pessoal")]
+    Personal,
+    /// Everyone in the guild can use it
+    #[name = "server"]
+    #[name_localized("pt-BR", "servidor")]
+    Guild,
+}
+
+/// The owner of a set of playlists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaylistOwner {
+    User(UserId),
+    Guild(GuildId),
+}
+
+impl PlaylistOwner {
+    /// The owner of the playlists of `scope`, for `user_id` in `guild_id`.
+    pub fn new(scope: PlaylistScope, user_id: UserId, guild_id: GuildId) -> Self {
+        match scope {
+            PlaylistScope::Personal => PlaylistOwner::User(user_id),
+            PlaylistScope::Guild => PlaylistOwner::Guild(guild_id),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedPlaylist {
+    /// The name as it was given, which is matched ignoring case
+    pub name: String,
+    pub songs: Vec<SongMetadata>,
+    /// Who saved the playlist, who can replace or delete it in a guild
+    #[serde(default)]
+    pub created_by: Option<UserId>,
+}
+
+/// The playlists of each owner, keyed by their lowercase names
+type Playlists = BTreeMap<String, SavedPlaylist>;
+
+/// The saved playlists, one JSON file per owner
+#[derive(Debug, Clone)]
+pub struct PlaylistStore {
+    dir: PathBuf,
+    /// Held while a file is changed, so that concurrent changes aren't lost
+    lock: Arc<Mutex<()>>,
+}
+
+impl PlaylistStore {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            dir: data_dir.join("playlists"),
+            lock: Default::default(),
+        }
+    }
+
+    fn path(&self, owner: PlaylistOwner) -> PathBuf {
+        match owner {
+            PlaylistOwner::User(user_id) => self.dir.join(format!("user-{user_id}.json")),
+            PlaylistOwner::Guild(guild_id) => self.dir.join(format!("guild-{guild_id}.json")),
+        }
+    }
+
+    async fn read(&self, owner: PlaylistOwner) -> Playlists {
+        read_json(&self.path(owner)).await.unwrap_or_default()
+    }
+
+    pub async fn get(&self, owner: PlaylistOwner, name: &str) -> Option<SavedPlaylist> {
+        self.read(owner).await.remove(&name.to_lowercase())
+    }
+
+    /// The owner's playlists, sorted by name.
+    pub async fn list(&self, owner: PlaylistOwner) -> Vec<SavedPlaylist> {
+        self.read(owner).await.into_values().collect()
+    }
+
+    /// Changes the owner's playlists with `func`, saving them afterwards.
+    async fn update<F, O>(&self, owner: PlaylistOwner, func: F) -> O
+    where
+        F: FnOnce(&mut Playlists) -> O,
+    {
+        let _guard = self.lock.lock().await;
+        let mut playlists = self.read(owner).await;
+        let output = func(&mut playlists);
+
+        if let Err(cause) = write_json(&self.path(owner), &playlists).await {
+            error!(%cause, ?owner, "failed to save playlists");
+        }
+        output
+    }
+
+    /// Stores `songs` as the playlist `name` saved by `user_id`, replacing
+    /// it if it exists.
+    pub async fn save(
+        &self,
+        owner: PlaylistOwner,
+        name: &str,
+        songs: Vec<SongMetadata>,
+        user_id: UserId,
+    ) {
+        let playlist = SavedPlaylist {
+            name: name.to_string(),
+            songs,
+            created_by: Some(user_id),
+        };
+        self.update(owner, |playlists| {
+            playlists.insert(name.to_lowercase(), playlist);
+        })
+        .await
+    }
+
+    /// Adds `songs` to the end of the playlist `name`, creating it for
+    /// `user_id` if needed, and returns how many songs it has.
+    pub async fn append(
+        &self,
+        owner: PlaylistOwner,
+        name: &str,
+        songs: Vec<SongMetadata>,
+        user_id: UserId,
+    ) -> usize {
+        self.update(owner, |playlists| {
+            let playlist = playlists
+                .entry(name.to_lowercase())
+                .or_insert_with(|| SavedPlaylist {
+                    name: name.to_string(),
+                    songs: Vec::new(),
+                    created_by: Some(user_id),
+                });
+            playlist.songs.extend(songs);
+            playlist.songs.len()
+        })
+        .await
+    }
+
+    /// Deletes the playlist `name`, returning it if it existed.
+    pub async fn delete(&self, owner: PlaylistOwner, name: &str) -> Option<SavedPlaylist> {
+        self.update(owner, |playlists| playlists.remove(&name.to_lowercase()))
+            .await
+    }
+}
+
+pub struct PlaylistStoreKey;
+
+impl TypeMapKey for PlaylistStoreKey {
+    type Value = PlaylistStore;
+}
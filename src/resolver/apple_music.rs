@@ -0,0 +1,182 @@
+use reqwest::Url;
+use serde_json::Value;
+use serenity::async_trait;
+use tracing::warn;
+
+use super::{
+    is_host,
+    streaming::{
+        fetch_page, json_scripts, parse_iso_duration, resolve_link, ExternalLink, ExternalTrack,
+    },
+    Resolution, ResolveContext, Resolver,
+};
+use crate::commands::BotError;
+
+/// Songs, albums and playlists, described by the schema.org metadata of
+/// their pages and played from YouTube.
+pub struct AppleMusicResolver;
+
+#[async_trait]
+impl Resolver for AppleMusicResolver {
+    fn name(&self) -> &'static str {
+        "apple-music"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        is_host(url, "music.apple.com")
+    }
+
+    async fn resolve(&self, url: &Url, ctx: &ResolveContext) -> Result<Resolution, BotError> {
+        let html = fetch_page(url.as_str(), ctx).await?;
+        let external = parse_page(&html, song_id(url).as_deref()).ok_or_else(|| {
+            warn!(%url, "the page has no metadata");
+            BotError::ResolveFailed
+        })?;
+
+        resolve_link(url, external, ctx).await
+    }
+}
+
+/// The song an album link points to, as in `/album/<name>/<id>?i=<song id>`.
+pub fn song_id(url: &Url) -> Option<String> {
+    url.query_pairs()
+        .find(|(key, _)| key == "i")
+        .map(|(_, id)| id.into_owned())
+}
+
+/// Reads the tracks from the page's metadata, picking the one with
+/// `song_id` if the page is of its album.
+pub fn parse_page(html: &str, song_id: Option<&str>) -> Option<ExternalLink> {
+    json_scripts(html).find_map(|metadata| {
+        match metadata["@type"].as_str()? {
+            // a song page describes the composition, which has a recording
+            "MusicComposition" => recording(&metadata["audio"], "").map(ExternalLink::Track),
+            "MusicRecording" => recording(&metadata, "").map(ExternalLink::Track),
+            kind @ ("MusicAlbum" | "MusicPlaylist") => {
+                let artist = artist_names(&metadata["byArtist"]).unwrap_or_default();
+                let list = match kind {
+                    "MusicAlbum" => &metadata["tracks"],
+                    _ => &metadata["track"],
+                };
+                let entries = list.as_array()?;
+
+                let song = song_id.and_then(|id| {
+                    let suffix = format!("/{id}");
+                    entries.iter().find(|entry| {
+                        entry["url"]
+                            .as_str()
+                            .is_some_and(|url| url.ends_with(&suffix))
+                    })
+                });
+                if let Some(song) = song {
+                    return recording(song, &artist).map(ExternalLink::Track);
+                }
+
+                Some(ExternalLink::Collection {
+                    name: metadata["name"].as_str()?.to_string(),
+                    tracks: entries
+                        .iter()
+                        .filter_map(|entry| recording(entry, &artist))
+                        .collect(),
+                })
+            }
+            _ => None,
+        }
+    })
+}
+
+/// Reads a `MusicRecording`, which may leave out the artists of its album.
+fn recording(metadata: &Value, album_artist: &str) -> Option<ExternalTrack> {
+    Some(ExternalTrack {
+        title: metadata["name"].as_str()?.to_string(),
+        artist: artist_names(&metadata["byArtist"]).unwrap_or_else(|| album_artist.to_string()),
+        duration: metadata["duration"].as_str().and_then(parse_iso_duration),
+    })
+}
+
+/// Joins the names of `byArtist`, which is either an artist or a list of
+/// them.
+fn artist_names(by_artist: &Value) -> Option<String> {
+    let names = match by_artist {
+        Value::Array(artists) => artists
+            .iter()
+            .filter_map(|artist| artist["name"].as_str())
+            .collect::<Vec<_>>(),
+        artist => vec![artist["name"].as_str()?],
+    };
+
+    (!names.is_empty()).then(|| names.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    const SONG_PAGE: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/apple_music_song.html"
+    ));
+    const ALBUM_PAGE: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/apple_music_album.html"
+    ));
+
+    #[test]
+    fn finds_song_id_of_album_links() {
+        let url =
+            Url::parse("https://music.apple.com/us/album/getz-gilberto/1440810086?i=1440810100")
+                .unwrap();
+        assert_eq!(song_id(&url).as_deref(), Some("1440810100"));
+
+        let url = Url::parse("https://music.apple.com/us/album/getz-gilberto/1440810086").unwrap();
+        assert_eq!(song_id(&url), None);
+    }
+
+    #[test]
+    fn parses_song_page() {
+        assert_eq!(
+            parse_page(SONG_PAGE, None),
+            Some(ExternalLink::Track(ExternalTrack {
+                title: "Garota de Ipanema".to_string(),
+                artist: "Stan Getz, João Gilberto".to_string(),
+                duration: Some(Duration::from_secs(324)),
+            }))
+        );
+    }
+
+    #[test]
+    fn parses_album_page() {
+        let Some(ExternalLink::Collection { name, tracks }) = parse_page(ALBUM_PAGE, None) else {
+            panic!("expected a collection");
+        };
+
+        assert_eq!(name, "Getz/Gilberto");
+        assert_eq!(tracks.len(), 3);
+        // the tracks without artists get the album's
+        assert_eq!(tracks[1].artist, "Stan Getz, João Gilberto");
+        assert_eq!(tracks[1].duration, Some(Duration::from_secs(166)));
+        assert_eq!(tracks[2].artist, "Astrud Gilberto");
+    }
+
+    #[test]
+    fn picks_song_of_album_page() {
+        assert_eq!(
+            parse_page(ALBUM_PAGE, Some("1440810100")),
+            Some(ExternalLink::Track(ExternalTrack {
+                title: "Doralice".to_string(),
+                artist: "Stan Getz, João Gilberto".to_string(),
+                duration: Some(Duration::from_secs(166)),
+            }))
+        );
+    }
+
+    #[test]
+    fn falls_back_to_album_for_unknown_song() {
+        assert!(matches!(
+            parse_page(ALBUM_PAGE, Some("1")),
+            Some(ExternalLink::Collection { .. })
+        ));
+    }
+}
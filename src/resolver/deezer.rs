@@ -0,0 +1,194 @@
+use std::time::Duration;
+
+use reqwest::Url;
+use serde::Deserialize;
+use serenity::async_trait;
+use tracing::warn;
+
+use super::{
+    is_host,
+    streaming::{fetch_page, follow_short_link, resolve_link, ExternalLink, ExternalTrack},
+    Resolution, ResolveContext, Resolver,
+};
+use crate::commands::BotError;
+
+/// Where Deezer describes its tracks, albums and playlists
+const DEEZER_API_URL: &str = "https://api.deezer.com";
+
+/// Tracks, albums and playlists, described by Deezer's public API and played
+/// from YouTube.
+pub struct DeezerResolver;
+
+#[async_trait]
+impl Resolver for DeezerResolver {
+    fn name(&self) -> &'static str {
+        "deezer"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        is_host(url, "deezer.com") || is_host(url, "deezer.page.link")
+    }
+
+    async fn resolve(&self, url: &Url, ctx: &ResolveContext) -> Result<Resolution, BotError> {
+        // the share links, such as `link.deezer.com/s/<code>`, redirect to
+        // the page of the track
+        let target = match parse_link(url) {
+            Some(_) => url.clone(),
+            None => follow_short_link(url, ctx).await?,
+        };
+        let (kind, id) = parse_link(&target).ok_or(BotError::InvalidQuery)?;
+
+        let json = fetch_page(&format!("{DEEZER_API_URL}/{kind}/{id}"), ctx).await?;
+        let external = parse_api(kind, &json).ok_or_else(|| {
+            warn!(%url, json, "unexpected Deezer API response");
+            BotError::ResolveFailed
+        })?;
+
+        resolve_link(url, external, ctx).await
+    }
+}
+
+/// Extracts the kind and id of a link such as
+/// `https://www.deezer.com/en/album/<id>`.
+pub fn parse_link(url: &Url) -> Option<(&str, &str)> {
+    let segments = url.path_segments()?.collect::<Vec<_>>();
+    segments.windows(2).find_map(|pair| {
+        let [kind, id] = pair else { return None };
+        let is_id = !id.is_empty() && id.chars().all(|c| c.is_ascii_digit());
+        (matches!(*kind, "track" | "album" | "playlist") && is_id).then_some((*kind, *id))
+    })
+}
+
+/// Reads the tracks from the API's response about a `kind`.
+pub fn parse_api(kind: &str, json: &str) -> Option<ExternalLink> {
+    if kind == "track" {
+        let track = serde_json::from_str::<ApiTrack>(json).ok()?;
+        return Some(ExternalLink::Track(track.into()));
+    }
+
+    let collection = serde_json::from_str::<ApiCollection>(json).ok()?;
+    Some(ExternalLink::Collection {
+        name: collection.title,
+        tracks: collection.tracks.data.into_iter().map(Into::into).collect(),
+    })
+}
+
+#[derive(Deserialize)]
+struct ApiTrack {
+    title: String,
+    /// In seconds
+    duration: u64,
+    artist: ApiArtist,
+}
+
+impl From<ApiTrack> for ExternalTrack {
+    fn from(track: ApiTrack) -> Self {
+        ExternalTrack {
+            title: track.title,
+            artist: track.artist.name,
+            duration: Some(Duration::from_secs(track.duration)),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ApiArtist {
+    name: String,
+}
+
+/// An album or a playlist
+#[derive(Deserialize)]
+struct ApiCollection {
+    title: String,
+    tracks: ApiTracks,
+}
+
+#[derive(Deserialize)]
+struct ApiTracks {
+    data: Vec<ApiTrack>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRACK_RESPONSE: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/deezer_track.json"
+    ));
+    const ALBUM_RESPONSE: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/deezer_album.json"
+    ));
+
+    fn url(text: &str) -> Url {
+        Url::parse(text).unwrap()
+    }
+
+    #[test]
+    fn parses_links() {
+        assert_eq!(
+            parse_link(&url("https://www.deezer.com/en/album/302127")),
+            Some(("album", "302127"))
+        );
+        assert_eq!(
+            parse_link(&url("https://www.deezer.com/track/3135556?utm_source=x")),
+            Some(("track", "3135556"))
+        );
+        assert_eq!(
+            parse_link(&url("https://www.deezer.com/pt/playlist/908622995")),
+            Some(("playlist", "908622995"))
+        );
+    }
+
+    #[test]
+    fn leaves_short_links_to_be_followed() {
+        assert_eq!(parse_link(&url("https://link.deezer.com/s/30ZQCx4x")), None);
+        assert_eq!(parse_link(&url("https://deezer.page.link/abcdef")), None);
+        assert_eq!(
+            parse_link(&url("https://www.deezer.com/en/artist/27")),
+            None
+        );
+    }
+
+    #[test]
+    fn parses_track_response() {
+        assert_eq!(
+            parse_api("track", TRACK_RESPONSE),
+            Some(ExternalLink::Track(ExternalTrack {
+                title: "Harder, Better, Faster, Stronger".to_string(),
+                artist: "Daft Punk".to_string(),
+                duration: Some(Duration::from_secs(224)),
+            }))
+        );
+    }
+
+    #[test]
+    fn parses_album_response() {
+        let Some(ExternalLink::Collection { name, tracks }) = parse_api("album", ALBUM_RESPONSE)
+        else {
+            panic!("expected a collection");
+        };
+
+        assert_eq!(name, "Discovery");
+        let titles = tracks
+            .iter()
+            .map(|track| track.title.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            titles,
+            [
+                "One More Time",
+                "Aerodynamic",
+                "Harder, Better, Faster, Stronger"
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_error_responses() {
+        let error = r#"{"error":{"type":"DataException","message":"no data","code":800}}"#;
+        assert_eq!(parse_api("track", error), None);
+        assert_eq!(parse_api("album", error), None);
+    }
+}
@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use reqwest::Url;
+use serenity::async_trait;
+
+use super::{Resolution, ResolveContext, Resolver};
+use crate::{
+    commands::BotError,
+    player::{AudioSource, SongMetadata},
+};
+
+/// The extensions of the files that are played without yt-dlp
+const AUDIO_EXTENSIONS: &[&str] = &["mp3", "m4a", "aac", "ogg", "opus", "flac", "wav"];
+
+/// Audio files, downloaded directly by songbird.
+pub struct DirectResolver;
+
+#[async_trait]
+impl Resolver for DirectResolver {
+    fn name(&self) -> &'static str {
+        "direct"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        file_name(url)
+            .and_then(|name| name.rsplit_once('.'))
+            .is_some_and(|(_, extension)| {
+                AUDIO_EXTENSIONS.contains(&extension.to_lowercase().as_str())
+            })
+    }
+
+    async fn resolve(&self, url: &Url, ctx: &ResolveContext) -> Result<Resolution, BotError> {
+        let name = file_name(url).map(percent_decode).unwrap_or_default();
+        let title = match name.rsplit_once('.') {
+            Some((title, _)) if !title.is_empty() => title.to_string(),
+            _ => url.to_string(),
+        };
+
+        Ok(Resolution::Songs(vec![SongMetadata {
+            title,
+            url: url.to_string(),
+            // the file isn't read until it starts playing
+            duration: Duration::ZERO,
+            thumbnail: None,
+            user: ctx.requester.clone(),
+            source: AudioSource::Http,
+            origin: None,
+            uploader: None,
+            autoplayed: false,
+            chapters: None,
+            cover: None,
+        }]))
+    }
+}
+
+/// The last segment of the URL's path.
+fn file_name(url: &Url) -> Option<&str> {
+    url.path_segments()?.last().filter(|name| !name.is_empty())
+}
+
+/// Decodes the `%XX` escapes of a URL's path segment.
+fn percent_decode(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
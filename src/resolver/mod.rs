@@ -0,0 +1,174 @@
+//! Turns the URLs and searches given to `/play` into the songs to enqueue.
+
+use std::time::Duration;
+
+use reqwest::{Client as HttpClient, Url};
+use serenity::async_trait;
+use tracing::{debug, error};
+
+use crate::{
+    commands::BotError,
+    player::{AudioSource, Chapter, SongMetadata},
+    tools::{self, piped::PipedInstances},
+};
+
+pub use self::{
+    apple_music::AppleMusicResolver,
+    deezer::DeezerResolver,
+    direct::DirectResolver,
+    spotify::SpotifyResolver,
+    youtube::{video_id, YoutubeResolver},
+    ytdlp::YtDlpResolver,
+};
+
+mod apple_music;
+mod deezer;
+mod direct;
+mod spotify;
+mod streaming;
+mod youtube;
+mod ytdlp;
+
+/// What the resolvers need to know about the request
+pub struct ResolveContext {
+    pub http: HttpClient,
+    pub piped: PipedInstances,
+    /// How many entries of a playlist are resolved at most
+    pub max_playlist_size: usize,
+    /// The name of who asked for the songs
+    pub requester: String,
+}
+
+/// The songs found by a resolver
+pub enum Resolution {
+    Songs(Vec<SongMetadata>),
+    Playlist {
+        name: String,
+        /// How many songs the playlist has, including the ones left out
+        total: usize,
+        songs: Vec<SongMetadata>,
+    },
+}
+
+impl Resolution {
+    pub fn into_songs(self) -> Vec<SongMetadata> {
+        match self {
+            Resolution::Songs(songs) => songs,
+            Resolution::Playlist { songs, .. } => songs,
+        }
+    }
+}
+
+/// A provider of songs, such as a website.
+#[async_trait]
+pub trait Resolver: Send + Sync {
+    /// The name shown in the logs
+    fn name(&self) -> &'static str;
+
+    /// Whether the resolver knows how to play `url`.
+    fn matches(&self, url: &Url) -> bool;
+
+    async fn resolve(&self, url: &Url, ctx: &ResolveContext) -> Result<Resolution, BotError>;
+
+    /// The prefix that searches this provider, such as `sc` for
+    /// `sc:artist song`.
+    fn search_prefix(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Searches the provider, resolving the best match.
+    async fn search(&self, _query: &str, _ctx: &ResolveContext) -> Result<Resolution, BotError> {
+        Err(BotError::InvalidQuery)
+    }
+}
+
+/// The known resolvers, tried in order
+pub struct Resolvers {
+    resolvers: Vec<Box<dyn Resolver>>,
+}
+
+impl Default for Resolvers {
+    fn default() -> Self {
+        Self {
+            resolvers: vec![
+                Box::new(YoutubeResolver),
+                Box::new(YtDlpResolver::SOUNDCLOUD),
+                Box::new(YtDlpResolver::BANDCAMP),
+                Box::new(SpotifyResolver),
+                Box::new(AppleMusicResolver),
+                Box::new(DeezerResolver),
+                Box::new(DirectResolver),
+                // it accepts any URL, so it must be the last one
+                Box::new(YtDlpResolver::GENERIC),
+            ],
+        }
+    }
+}
+
+impl Resolvers {
+    /// Resolves `url` with the first resolver that matches it.
+    pub async fn resolve(&self, url: &Url, ctx: &ResolveContext) -> Result<Resolution, BotError> {
+        let resolver = self
+            .resolvers
+            .iter()
+            .find(|resolver| resolver.matches(url))
+            .ok_or(BotError::InvalidQuery)?;
+
+        debug!(resolver = resolver.name(), %url, "resolving URL");
+        resolver.resolve(url, ctx).await
+    }
+
+    /// The resolver that searches with `prefix`.
+    pub fn for_prefix(&self, prefix: &str) -> Option<&dyn Resolver> {
+        self.resolvers
+            .iter()
+            .find(|resolver| resolver.search_prefix() == Some(prefix))
+            .map(Box::as_ref)
+    }
+}
+
+/// Asks yt-dlp about the song at `url`, which may also be a yt-dlp search
+/// such as `scsearch1:artist song`.
+async fn ytdlp_metadata(url: &str, ctx: &ResolveContext) -> Result<SongMetadata, BotError> {
+    let info = tools::ytdlp::info(url).await.map_err(|cause| {
+        error!(%cause, url, "yt-dlp failed");
+        BotError::ResolveFailed
+    })?;
+
+    // videos without chapters have none in the info, and they shouldn't be
+    // looked up again elsewhere
+    let chapters = info
+        .chapters
+        .iter()
+        .flatten()
+        .map(|chapter| Chapter {
+            title: chapter.title.clone(),
+            start: Duration::try_from_secs_f64(chapter.start_time).unwrap_or_default(),
+        })
+        .collect();
+
+    Ok(SongMetadata {
+        duration: info.duration().unwrap_or(Duration::ZERO),
+        title: info.title.unwrap_or_else(|| url.to_string()),
+        // searches are replaced by the page of the song that was found
+        url: info.webpage_url.unwrap_or_else(|| url.to_string()),
+        thumbnail: info.thumbnail,
+        user: ctx.requester.clone(),
+        source: AudioSource::YtDlp,
+        origin: None,
+        uploader: info.channel.or(info.artist).or(info.uploader),
+        autoplayed: false,
+        chapters: Some(chapters),
+        cover: None,
+    })
+}
+
+/// Whether `url`'s host is `domain` or one of its subdomains.
+fn is_host(url: &Url, domain: &str) -> bool {
+    url.host_str().is_some_and(|host| {
+        host == domain
+            || host
+                .strip_suffix(domain)
+                .is_some_and(|subdomain| subdomain.ends_with('.'))
+    })
+}
@@ -0,0 +1,210 @@
+use std::time::Duration;
+
+use reqwest::Url;
+use serde::Deserialize;
+use serenity::async_trait;
+use tracing::warn;
+
+use super::{
+    is_host,
+    streaming::{
+        fetch_page, follow_short_link, json_scripts, resolve_link, ExternalLink, ExternalTrack,
+    },
+    Resolution, ResolveContext, Resolver,
+};
+use crate::commands::BotError;
+
+/// Tracks, albums and playlists, described by Spotify's embed player and
+/// played from YouTube.
+pub struct SpotifyResolver;
+
+#[async_trait]
+impl Resolver for SpotifyResolver {
+    fn name(&self) -> &'static str {
+        "spotify"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        is_host(url, "open.spotify.com") || is_host(url, "spotify.link")
+    }
+
+    async fn resolve(&self, url: &Url, ctx: &ResolveContext) -> Result<Resolution, BotError> {
+        let target = if is_host(url, "spotify.link") {
+            follow_short_link(url, ctx).await?
+        } else {
+            url.clone()
+        };
+        let (kind, id) = parse_link(&target).ok_or(BotError::InvalidQuery)?;
+
+        let html = fetch_page(&format!("https://open.spotify.com/embed/{kind}/{id}"), ctx).await?;
+        let external = parse_embed(&html).ok_or_else(|| {
+            warn!(%url, "the embed page has no metadata");
+            BotError::ResolveFailed
+        })?;
+
+        resolve_link(url, external, ctx).await
+    }
+}
+
+/// Extracts the kind and id of a link such as
+/// `https://open.spotify.com/intl-pt/album/<id>`.
+pub fn parse_link(url: &Url) -> Option<(&str, &str)> {
+    let mut segments = url
+        .path_segments()?
+        .filter(|segment| !segment.is_empty() && !segment.starts_with("intl-"));
+
+    let kind = segments.next()?;
+    let id = segments.next()?;
+    matches!(kind, "track" | "album" | "playlist").then_some((kind, id))
+}
+
+/// Reads the tracks from the data the embed page is rendered from.
+pub fn parse_embed(html: &str) -> Option<ExternalLink> {
+    let entity = json_scripts(html).find_map(|mut data| {
+        let entity = data
+            .pointer_mut("/props/pageProps/state/data/entity")?
+            .take();
+        serde_json::from_value::<Entity>(entity).ok()
+    })?;
+
+    if entity.kind == "track" {
+        let artist = entity
+            .artists
+            .iter()
+            .map(|artist| artist.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        return Some(ExternalLink::Track(ExternalTrack {
+            title: entity.name,
+            artist,
+            duration: entity.duration.map(Duration::from_millis),
+        }));
+    }
+
+    let tracks = entity
+        .track_list
+        .into_iter()
+        .map(|track| ExternalTrack {
+            title: track.title,
+            // the artists are separated by non-breaking spaces
+            artist: track.subtitle.replace('\u{a0}', " "),
+            duration: track.duration.map(Duration::from_millis),
+        })
+        .collect();
+
+    Some(ExternalLink::Collection {
+        name: entity.name,
+        tracks,
+    })
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Entity {
+    #[serde(rename = "type")]
+    kind: String,
+    name: String,
+    #[serde(default)]
+    artists: Vec<Artist>,
+    /// In milliseconds
+    duration: Option<u64>,
+    #[serde(default)]
+    track_list: Vec<ListedTrack>,
+}
+
+#[derive(Deserialize)]
+struct Artist {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct ListedTrack {
+    title: String,
+    /// The artists of the track
+    #[serde(default)]
+    subtitle: String,
+    /// In milliseconds
+    duration: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRACK_EMBED: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/spotify_track_embed.html"
+    ));
+    const ALBUM_EMBED: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/spotify_album_embed.html"
+    ));
+
+    fn url(text: &str) -> Url {
+        Url::parse(text).unwrap()
+    }
+
+    #[test]
+    fn parses_links() {
+        let link = url("https://open.spotify.com/track/4uLU6hMCjMI75M1A2tKUQC?si=abc");
+        assert_eq!(parse_link(&link), Some(("track", "4uLU6hMCjMI75M1A2tKUQC")));
+
+        let link = url("https://open.spotify.com/intl-pt/album/0fQnTWWBh6iEJTWpxkMfbO");
+        assert_eq!(parse_link(&link), Some(("album", "0fQnTWWBh6iEJTWpxkMfbO")));
+
+        let link = url("https://open.spotify.com/playlist/37i9dQZF1DXcBWIGoYBM5M");
+        assert_eq!(
+            parse_link(&link),
+            Some(("playlist", "37i9dQZF1DXcBWIGoYBM5M"))
+        );
+    }
+
+    #[test]
+    fn rejects_links_without_tracks() {
+        assert_eq!(
+            parse_link(&url(
+                "https://open.spotify.com/artist/0oSGxfWSnnOXhD2fKuz2Gy"
+            )),
+            None
+        );
+        assert_eq!(parse_link(&url("https://open.spotify.com/track")), None);
+        assert_eq!(parse_link(&url("https://open.spotify.com/")), None);
+    }
+
+    #[test]
+    fn parses_track_embed() {
+        assert_eq!(
+            parse_embed(TRACK_EMBED),
+            Some(ExternalLink::Track(ExternalTrack {
+                title: "Águas de Março".to_string(),
+                artist: "Elis Regina, Tom Jobim".to_string(),
+                duration: Some(Duration::from_secs(212)),
+            }))
+        );
+    }
+
+    #[test]
+    fn parses_album_embed() {
+        let Some(ExternalLink::Collection { name, tracks }) = parse_embed(ALBUM_EMBED) else {
+            panic!("expected a collection");
+        };
+
+        assert_eq!(name, "Elis & Tom");
+        assert_eq!(tracks.len(), 3);
+        assert_eq!(
+            tracks[0],
+            ExternalTrack {
+                title: "Águas de Março".to_string(),
+                artist: "Elis Regina, Tom Jobim".to_string(),
+                duration: Some(Duration::from_secs(212)),
+            }
+        );
+        assert_eq!(tracks[2].duration, None);
+    }
+
+    #[test]
+    fn ignores_pages_without_embed_data() {
+        assert_eq!(parse_embed("<html><body>Not found</body></html>"), None);
+    }
+}
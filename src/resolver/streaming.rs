@@ -0,0 +1,404 @@
+//! What the streaming services without a public audio source have in common:
+//! their tracks are described by the pasted link and played from the YouTube
+//! video that matches them the best.
+
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use reqwest::{header::USER_AGENT, Url};
+use serenity::futures::{stream, StreamExt};
+use tracing::{error, info, warn};
+
+use super::{Resolution, ResolveContext};
+use crate::{
+    commands::BotError,
+    player::{AudioSource, SongMetadata},
+    tools::piped::{PipedClient, StreamItem},
+};
+
+/// How long the pages of the services may take to load
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+/// The pages only embed their metadata for browsers
+const BROWSER_USER_AGENT: &str =
+    "Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0";
+/// How many search results are compared to a track
+const MATCH_CANDIDATES: usize = 5;
+/// How far a video's duration may be from the track's, at least
+const DURATION_TOLERANCE: Duration = Duration::from_secs(5);
+/// How many tracks of an album or playlist are searched at the same time
+const SEARCH_CONCURRENCY: usize = 4;
+
+lazy_static! {
+    static ref JSON_SCRIPT: Regex =
+        Regex::new(r#"(?s)<script[^>]*type="?application/(?:ld\+)?json"?[^>]*>(.*?)</script>"#)
+            .unwrap();
+}
+
+/// A track as described by a streaming service
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalTrack {
+    pub title: String,
+    /// The artists, separated by commas, which may be empty if unknown
+    pub artist: String,
+    pub duration: Option<Duration>,
+}
+
+impl ExternalTrack {
+    /// The name the track is shown with, and searched by.
+    fn display_name(&self) -> String {
+        if self.artist.is_empty() {
+            self.title.clone()
+        } else {
+            format!("{} - {}", self.artist, self.title)
+        }
+    }
+}
+
+/// The tracks behind a streaming service link
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExternalLink {
+    Track(ExternalTrack),
+    /// An album or a playlist
+    Collection {
+        name: String,
+        tracks: Vec<ExternalTrack>,
+    },
+}
+
+/// Finds the YouTube videos of the tracks behind `link`, which was the URL
+/// given to `/play`.
+pub async fn resolve_link(
+    link: &Url,
+    external: ExternalLink,
+    ctx: &ResolveContext,
+) -> Result<Resolution, BotError> {
+    let client = PipedClient::new(&ctx.http, &ctx.piped);
+
+    match external {
+        ExternalLink::Track(track) => {
+            let song = find_on_youtube(&client, &track, link, ctx)
+                .await?
+                .ok_or(BotError::NoResults)?;
+            Ok(Resolution::Songs(vec![song]))
+        }
+        ExternalLink::Collection { name, tracks } => {
+            let total = tracks.len();
+            // the searches are created beforehand, as closures borrowing the
+            // context aren't general enough for the resolvers' futures
+            let searches = tracks
+                .iter()
+                .take(ctx.max_playlist_size)
+                .map(|track| find_on_youtube(&client, track, link, ctx))
+                .collect::<Vec<_>>();
+            let songs = stream::iter(searches)
+                .buffered(SEARCH_CONCURRENCY)
+                .collect::<Vec<_>>()
+                .await
+                .into_iter()
+                // a single track that can't be found shouldn't prevent the
+                // rest of the playlist from playing
+                .filter_map(|found| found.ok().flatten())
+                .collect::<Vec<_>>();
+
+            info!(name, total, found = songs.len(), %link, "resolved collection");
+
+            if songs.is_empty() {
+                return Err(BotError::EmptyPlaylist);
+            }
+
+            Ok(Resolution::Playlist { name, total, songs })
+        }
+    }
+}
+
+/// Searches YouTube for `track`, returning `None` if none of the videos
+/// matches it.
+async fn find_on_youtube(
+    client: &PipedClient<'_>,
+    track: &ExternalTrack,
+    link: &Url,
+    ctx: &ResolveContext,
+) -> Result<Option<SongMetadata>, BotError> {
+    let name = track.display_name();
+    let candidates = client
+        .search_songs(&name)
+        .await
+        .map_err(|cause| {
+            error!(%cause, name, "failed to search the track");
+            BotError::SearchFailed(cause)
+        })?
+        .into_streams()
+        .filter(|stream| !stream.is_short)
+        .take(MATCH_CANDIDATES)
+        .collect::<Vec<_>>();
+
+    let Some(stream) = best_match(track, &candidates) else {
+        warn!(name, "no video matches the track");
+        return Ok(None);
+    };
+
+    Ok(Some(SongMetadata {
+        title: name,
+        url: format!("https://www.youtube.com{}", stream.url),
+        duration: stream.duration().unwrap_or_default(),
+        thumbnail: stream.thumbnail.clone(),
+        user: ctx.requester.clone(),
+        source: AudioSource::YtDlp,
+        origin: Some(link.to_string()),
+        uploader: Some(track.artist.clone()).filter(|artist| !artist.is_empty()),
+        autoplayed: false,
+        chapters: None,
+        cover: None,
+    }))
+}
+
+/// Picks the most relevant of the `candidates` whose duration is close to
+/// the track's, or the closest one if none is.
+///
+/// Live streams are never picked, since they can't be the track.
+pub fn best_match<'c>(
+    track: &ExternalTrack,
+    candidates: &'c [StreamItem],
+) -> Option<&'c StreamItem> {
+    let mut recorded = candidates
+        .iter()
+        .filter_map(|candidate| Some((candidate, candidate.duration()?)));
+
+    let Some(duration) = track.duration else {
+        return recorded.next().map(|(candidate, _)| candidate);
+    };
+
+    // longer tracks get some more leeway, as their videos often have intros
+    let tolerance = DURATION_TOLERANCE.max(duration / 20);
+    let distances = recorded
+        .map(|(candidate, found)| (candidate, distance(found, duration)))
+        .collect::<Vec<_>>();
+
+    distances
+        .iter()
+        .find(|(_, distance)| *distance <= tolerance)
+        .or_else(|| distances.iter().min_by_key(|(_, distance)| *distance))
+        .map(|(candidate, _)| *candidate)
+}
+
+fn distance(a: Duration, b: Duration) -> Duration {
+    a.max(b) - a.min(b)
+}
+
+/// Downloads the page at `url` as a browser would.
+pub async fn fetch_page(url: &str, ctx: &ResolveContext) -> Result<String, BotError> {
+    let response = ctx
+        .http
+        .get(url)
+        .header(USER_AGENT, BROWSER_USER_AGENT)
+        .timeout(FETCH_TIMEOUT)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status());
+
+    let body = match response {
+        Ok(response) => response.text().await,
+        Err(cause) => Err(cause),
+    };
+
+    body.map_err(|cause| {
+        error!(%cause, url, "failed to fetch the page");
+        BotError::ResolveFailed
+    })
+}
+
+/// Follows the redirects of a short link, returning where it leads.
+pub async fn follow_short_link(url: &Url, ctx: &ResolveContext) -> Result<Url, BotError> {
+    ctx.http
+        .get(url.clone())
+        .header(USER_AGENT, BROWSER_USER_AGENT)
+        .timeout(FETCH_TIMEOUT)
+        .send()
+        .await
+        .map(|response| response.url().clone())
+        .map_err(|cause| {
+            error!(%cause, %url, "failed to follow the short link");
+            BotError::ResolveFailed
+        })
+}
+
+/// The contents of the JSON `<script>` elements of a page, such as the
+/// `application/ld+json` metadata.
+pub fn json_scripts(html: &str) -> impl Iterator<Item = serde_json::Value> + '_ {
+    JSON_SCRIPT
+        .captures_iter(html)
+        .filter_map(|captures| serde_json::from_str(captures.get(1)?.as_str()).ok())
+}
+
+/// Parses an ISO 8601 duration such as `PT1H2M3S`.
+pub fn parse_iso_duration(text: &str) -> Option<Duration> {
+    let time = text.strip_prefix("PT")?;
+    let mut seconds = 0.0;
+    let mut number = String::new();
+
+    for c in time.chars() {
+        let unit = match c {
+            'H' => 3600.0,
+            'M' => 60.0,
+            'S' => 1.0,
+            _ => {
+                number.push(c);
+                continue;
+            }
+        };
+        seconds += number.parse::<f64>().ok()? * unit;
+        number.clear();
+    }
+
+    if !number.is_empty() {
+        return None;
+    }
+    // the page could have negative or huge numbers
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(duration: Option<u64>) -> ExternalTrack {
+        ExternalTrack {
+            title: "Song".to_string(),
+            artist: "Artist".to_string(),
+            duration: duration.map(Duration::from_secs),
+        }
+    }
+
+    fn candidate(id: &str, duration: i64) -> StreamItem {
+        StreamItem {
+            url: format!("/watch?v={id}"),
+            title: id.to_string(),
+            thumbnail: None,
+            uploader_name: None,
+            uploaded: None,
+            duration,
+            views: 0,
+            is_short: false,
+        }
+    }
+
+    fn best_id(track: &ExternalTrack, candidates: &[StreamItem]) -> Option<String> {
+        best_match(track, candidates).map(|stream| stream.title.clone())
+    }
+
+    #[test]
+    fn extracts_json_scripts() {
+        let html = r#"
+            <script type="application/ld+json">{"@type": "MusicRecording"}</script>
+            <script id="__NEXT_DATA__" type="application/json">{"page": 1}</script>
+            <script type="text/javascript">var x = {"ignored": true};</script>
+            <script type="application/json">not json</script>
+        "#;
+
+        let scripts = json_scripts(html).collect::<Vec<_>>();
+        assert_eq!(
+            scripts,
+            [
+                serde_json::json!({"@type": "MusicRecording"}),
+                serde_json::json!({"page": 1}),
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_multiline_json_scripts() {
+        let html = "<script type=\"application/ld+json\">\n{\n\"name\": \"x\"\n}\n</script>";
+        assert_eq!(
+            json_scripts(html).collect::<Vec<_>>(),
+            [serde_json::json!({"name": "x"})]
+        );
+    }
+
+    #[test]
+    fn parses_iso_durations() {
+        assert_eq!(
+            parse_iso_duration("PT1H2M3S"),
+            Some(Duration::from_secs(3723))
+        );
+        assert_eq!(parse_iso_duration("PT4M"), Some(Duration::from_secs(240)));
+        assert_eq!(
+            parse_iso_duration("PT3.5S"),
+            Some(Duration::from_millis(3500))
+        );
+        assert_eq!(parse_iso_duration("PT0S"), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn rejects_invalid_iso_durations() {
+        assert_eq!(parse_iso_duration("P1D"), None);
+        assert_eq!(parse_iso_duration("PT3"), None);
+        assert_eq!(parse_iso_duration("PTxS"), None);
+        assert_eq!(parse_iso_duration("3M"), None);
+        assert_eq!(parse_iso_duration("PT-5S"), None);
+        assert_eq!(parse_iso_duration("PT1e400S"), None);
+        assert_eq!(parse_iso_duration("PTNaNS"), None);
+    }
+
+    #[test]
+    fn prefers_most_relevant_within_tolerance() {
+        let candidates = [
+            candidate("far", 400),
+            candidate("near", 203),
+            candidate("exact", 200),
+        ];
+        // the second result is close enough, so the more exact one after it
+        // isn't needed
+        assert_eq!(
+            best_id(&track(Some(200)), &candidates).as_deref(),
+            Some("near")
+        );
+    }
+
+    #[test]
+    fn falls_back_to_closest_duration() {
+        let candidates = [
+            candidate("far", 400),
+            candidate("closer", 260),
+            candidate("closest", 230),
+        ];
+        assert_eq!(
+            best_id(&track(Some(200)), &candidates).as_deref(),
+            Some("closest")
+        );
+    }
+
+    #[test]
+    fn scales_tolerance_with_duration() {
+        // 5% of 10 minutes is 30 seconds
+        let candidates = [candidate("intro", 625), candidate("exact", 600)];
+        assert_eq!(
+            best_id(&track(Some(600)), &candidates).as_deref(),
+            Some("intro")
+        );
+
+        let candidates = [candidate("long", 635), candidate("exact", 600)];
+        assert_eq!(
+            best_id(&track(Some(600)), &candidates).as_deref(),
+            Some("exact")
+        );
+    }
+
+    #[test]
+    fn never_picks_live_streams() {
+        let candidates = [candidate("live", -1), candidate("video", 900)];
+        assert_eq!(
+            best_id(&track(Some(200)), &candidates).as_deref(),
+            Some("video")
+        );
+        assert_eq!(best_id(&track(None), &candidates).as_deref(), Some("video"));
+        assert_eq!(best_id(&track(Some(200)), &[candidate("live", -1)]), None);
+    }
+
+    #[test]
+    fn picks_first_without_track_duration() {
+        let candidates = [candidate("first", 100), candidate("second", 200)];
+        assert_eq!(best_id(&track(None), &candidates).as_deref(), Some("first"));
+        assert_eq!(best_id(&track(None), &[]), None);
+    }
+}
@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use reqwest::Url;
+use serenity::async_trait;
+use tracing::info;
+
+use super::{is_host, ytdlp_metadata, Resolution, ResolveContext, Resolver};
+use crate::{
+    commands::BotError,
+    player::{AudioSource, SongMetadata},
+    tools::piped::PipedClient,
+};
+
+/// Videos through yt-dlp, and playlists through Piped.
+///
+/// YouTube searches go through the `/play` picker instead.
+pub struct YoutubeResolver;
+
+#[async_trait]
+impl Resolver for YoutubeResolver {
+    fn name(&self) -> &'static str {
+        "youtube"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        is_host(url, "youtube.com") || is_host(url, "youtu.be")
+    }
+
+    async fn resolve(&self, url: &Url, ctx: &ResolveContext) -> Result<Resolution, BotError> {
+        match playlist_id(url) {
+            Some(id) => resolve_playlist(&id, ctx).await,
+            None => Ok(Resolution::Songs(vec![
+                ytdlp_metadata(url.as_str(), ctx).await?,
+            ])),
+        }
+    }
+}
+
+/// Fetches the videos of the playlist, up to the configured limit.
+async fn resolve_playlist(id: &str, ctx: &ResolveContext) -> Result<Resolution, BotError> {
+    let playlist = PipedClient::new(&ctx.http, &ctx.piped)
+        .playlist(id, ctx.max_playlist_size)
+        .await
+        .map_err(BotError::PlaylistFailed)?;
+
+    info!(
+        id,
+        name = playlist.name,
+        videos = playlist.related_streams.len(),
+        "resolved playlist"
+    );
+
+    if playlist.related_streams.is_empty() {
+        return Err(BotError::EmptyPlaylist);
+    }
+
+    let songs = playlist
+        .related_streams
+        .into_iter()
+        .map(|item| SongMetadata {
+            title: item.title,
+            url: format!("https://www.youtube.com{}", item.url),
+            duration: Duration::from_secs(item.duration.max(0) as u64),
+            thumbnail: item.thumbnail,
+            user: ctx.requester.clone(),
+            source: AudioSource::YtDlp,
+            origin: None,
+            uploader: item.uploader_name,
+            autoplayed: false,
+            chapters: None,
+            cover: None,
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Resolution::Playlist {
+        name: playlist.name,
+        total: playlist.videos.max(songs.len()),
+        songs,
+    })
+}
+
+/// Extracts the id of the video from a YouTube URL, such as
+/// `https://www.youtube.com/watch?v=<id>` or `https://youtu.be/<id>`.
+pub fn video_id(url: &Url) -> Option<String> {
+    if is_host(url, "youtu.be") {
+        return url
+            .path_segments()?
+            .next()
+            .filter(|id| !id.is_empty())
+            .map(String::from);
+    }
+    if !is_host(url, "youtube.com") {
+        return None;
+    }
+
+    let mut segments = url.path_segments()?;
+    match segments.next()? {
+        "watch" => url
+            .query_pairs()
+            .find(|(key, _)| key == "v")
+            .map(|(_, id)| id.into_owned()),
+        "shorts" | "live" | "embed" => segments.next().map(String::from),
+        _ => None,
+    }
+    .filter(|id| !id.is_empty())
+}
+
+/// Extracts the playlist id from a YouTube URL, ignoring the automatically
+/// generated mixes, which can't be expanded.
+fn playlist_id(url: &Url) -> Option<String> {
+    url.query_pairs()
+        .find(|(key, _)| key == "list")
+        .map(|(_, id)| id.into_owned())
+        .filter(|id| !id.is_empty() && !id.starts_with("RD"))
+}
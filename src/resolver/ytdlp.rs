@@ -0,0 +1,69 @@
+use reqwest::Url;
+use serenity::async_trait;
+
+use super::{is_host, ytdlp_metadata, Resolution, ResolveContext, Resolver};
+use crate::commands::BotError;
+
+/// Tracks of the sites that are only played through yt-dlp.
+pub struct YtDlpResolver {
+    name: &'static str,
+    /// The domains of the site, or none for any page
+    domains: &'static [&'static str],
+    /// The search prefix and the yt-dlp search of the site, if it has one
+    search: Option<(&'static str, &'static str)>,
+}
+
+impl YtDlpResolver {
+    pub const SOUNDCLOUD: Self = Self {
+        name: "soundcloud",
+        domains: &["soundcloud.com", "snd.sc"],
+        search: Some(("sc", "scsearch1")),
+    };
+    /// Bandcamp can't be searched by yt-dlp, so there's no search prefix.
+    pub const BANDCAMP: Self = Self {
+        name: "bandcamp",
+        domains: &["bandcamp.com"],
+        search: None,
+    };
+    /// Any other page, in the hope that yt-dlp supports it.
+    pub const GENERIC: Self = Self {
+        name: "yt-dlp",
+        domains: &[],
+        search: None,
+    };
+}
+
+#[async_trait]
+impl Resolver for YtDlpResolver {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        if self.domains.is_empty() {
+            matches!(url.scheme(), "http" | "https")
+        } else {
+            self.domains.iter().any(|domain| is_host(url, domain))
+        }
+    }
+
+    async fn resolve(&self, url: &Url, ctx: &ResolveContext) -> Result<Resolution, BotError> {
+        Ok(Resolution::Songs(vec![
+            ytdlp_metadata(url.as_str(), ctx).await?,
+        ]))
+    }
+
+    fn search_prefix(&self) -> Option<&'static str> {
+        self.search.map(|(prefix, _)| prefix)
+    }
+
+    async fn search(&self, query: &str, ctx: &ResolveContext) -> Result<Resolution, BotError> {
+        let Some((_, search)) = self.search else {
+            return Err(BotError::InvalidQuery);
+        };
+        let song = ytdlp_metadata(&format!("{search}:{query}"), ctx)
+            .await
+            .map_err(|_| BotError::NoResults)?;
+        Ok(Resolution::Songs(vec![song]))
+    }
+}
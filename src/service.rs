@@ -1,16 +1,27 @@
 use poise::Command;
 use reqwest::Client as HttpClient;
 use serenity::{
+    all::FullEvent,
     prelude::{GatewayIntents, TypeMapKey},
     Client as SerenityClient,
 };
 use shuttle_serenity::SerenityService;
 use songbird::SerenityInit;
 
-use crate::commands;
+use crate::{
+    commands::{self, SongSuggestionsKey},
+    config::{Config, ConfigKey},
+    events::voice,
+    guild::GuildSettingsKey,
+    history::HistoryKey,
+    playlists::{PlaylistStore, PlaylistStoreKey},
+    session::{self, SessionStore, SessionStoreKey},
+    tools::piped::PipedInstances,
+};
 
 pub struct CreateService {
     token: String,
+    config: Config,
     commands: Vec<Command<(), commands::Error>>,
 }
 
@@ -18,24 +29,43 @@ impl CreateService {
     pub fn new(token: String) -> Self {
         Self {
             token,
+            config: Config::default(),
             commands: Vec::new(),
         }
     }
 
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
     pub fn with_command(mut self, cmd: Command<(), commands::Error>) -> Self {
         self.commands.push(cmd);
         self
     }
 
     pub async fn build(self) -> SerenityService {
+        let save_interval = self.config.session_save_interval;
         let framework: poise::Framework<(), anyhow::Error> = poise::Framework::builder()
             .options(poise::FrameworkOptions {
                 commands: self.commands,
+                on_error: |error| Box::pin(commands::on_error(error)),
+                event_handler: |ctx, event, _framework, _data| {
+                    Box::pin(async move {
+                        if let FullEvent::VoiceStateUpdate { new, .. } = event {
+                            voice::leave_if_alone(ctx, new).await;
+                        }
+                        Ok(())
+                    })
+                },
                 ..Default::default()
             })
-            .setup(|ctx, _, framework| {
+            .setup(move |ctx, _, framework| {
                 Box::pin(async move {
                     poise::builtins::register_globally(ctx, &framework.options().commands).await?;
+
+                    session::offer_resume(ctx).await;
+                    tokio::spawn(session::save_periodically(ctx.clone(), save_interval));
                     Ok(())
                 })
             })
@@ -47,6 +77,16 @@ impl CreateService {
             .framework(framework)
             .register_songbird()
             .type_map_insert::<HttpKey>(HttpClient::new())
+            .type_map_insert::<PipedKey>(PipedInstances::new(
+                self.config.piped_instances.clone(),
+                self.config.piped_cooldown,
+            ))
+            .type_map_insert::<GuildSettingsKey>(Default::default())
+            .type_map_insert::<HistoryKey>(Default::default())
+            .type_map_insert::<SongSuggestionsKey>(Default::default())
+            .type_map_insert::<SessionStoreKey>(SessionStore::new(&self.config.data_dir))
+            .type_map_insert::<PlaylistStoreKey>(PlaylistStore::new(&self.config.data_dir))
+            .type_map_insert::<ConfigKey>(self.config)
             .await
             .expect("Err creating client");
 
@@ -59,3 +99,9 @@ pub struct HttpKey;
 impl TypeMapKey for HttpKey {
     type Value = HttpClient;
 }
+
+pub struct PipedKey;
+
+impl TypeMapKey for PipedKey {
+    type Value = PipedInstances;
+}
@@ -0,0 +1,238 @@
+//! The queue of each guild, saved so that it can be resumed after a restart.
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::{ChannelId, GuildId},
+    prelude::{Context, TypeMapKey},
+};
+use songbird::tracks::TrackHandle;
+use tokio::fs;
+use tracing::{error, info};
+
+use crate::{
+    guild::{GuildSettings, GuildSettingsKey, LoopMode, SponsorCategory},
+    i18n::{t, Locale},
+    player::{SongMetadata, SongMetadataKey},
+    storage::{read_json, remove_file, write_json},
+};
+
+/// What is needed to rebuild a guild's queue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    /// The voice channel the bot was in
+    pub voice_channel: ChannelId,
+    /// The queue, starting with the current track
+    pub tracks: Vec<SongMetadata>,
+    /// How far into the current track it was
+    pub position: Duration,
+    pub loop_mode: LoopMode,
+    pub volume: u16,
+    pub last_channel: Option<ChannelId>,
+    pub locale: Option<Locale>,
+    #[serde(default)]
+    pub autoplay: bool,
+    #[serde(default = "default_sponsor_categories")]
+    pub sponsor_categories: Vec<SponsorCategory>,
+}
+
+impl Session {
+    /// Describes the queue of a call in `voice_channel`, returning `None`
+    /// if there's nothing to resume.
+    pub async fn capture(
+        voice_channel: ChannelId,
+        queue: &[TrackHandle],
+        settings: &GuildSettings,
+    ) -> Option<Self> {
+        let mut tracks = Vec::with_capacity(queue.len());
+        for track in queue {
+            let map = track.typemap().read().await;
+            if let Some(meta) = map.get::<SongMetadataKey>() {
+                tracks.push(meta.clone());
+            }
+        }
+        if tracks.is_empty() {
+            return None;
+        }
+
+        let position = match queue.first() {
+            Some(current) => current
+                .get_info()
+                .await
+                .map(|info| info.position)
+                .unwrap_or_default(),
+            None => Duration::ZERO,
+        };
+
+        Some(Self {
+            voice_channel,
+            tracks,
+            position,
+            loop_mode: settings.loop_mode,
+            volume: settings.volume,
+            last_channel: settings.last_channel,
+            locale: settings.locale,
+            autoplay: settings.autoplay,
+            sponsor_categories: settings.sponsor_categories.clone(),
+        })
+    }
+
+    /// Puts the saved settings back into the guild's.
+    pub fn restore_settings(&self, settings: &mut GuildSettings) {
+        settings.loop_mode = self.loop_mode;
+        settings.volume = self.volume;
+        settings.last_channel = self.last_channel;
+        settings.locale = self.locale;
+        settings.autoplay = self.autoplay;
+        settings.sponsor_categories = self.sponsor_categories.clone();
+    }
+}
+
+fn default_sponsor_categories() -> Vec<SponsorCategory> {
+    GuildSettings::default().sponsor_categories
+}
+
+/// The saved sessions, one JSON file per guild
+#[derive(Debug, Clone)]
+pub struct SessionStore {
+    dir: PathBuf,
+}
+
+impl SessionStore {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            dir: data_dir.join("sessions"),
+        }
+    }
+
+    fn path(&self, guild_id: GuildId) -> PathBuf {
+        self.dir.join(format!("{guild_id}.json"))
+    }
+
+    pub async fn load(&self, guild_id: GuildId) -> Option<Session> {
+        read_json(&self.path(guild_id)).await
+    }
+
+    pub async fn save(&self, guild_id: GuildId, session: &Session) {
+        if let Err(cause) = write_json(&self.path(guild_id), session).await {
+            error!(%cause, guild_id = guild_id.get(), "failed to save session");
+        }
+    }
+
+    pub async fn remove(&self, guild_id: GuildId) {
+        if let Err(cause) = remove_file(&self.path(guild_id)).await {
+            error!(%cause, guild_id = guild_id.get(), "failed to remove session");
+        }
+    }
+
+    /// Loads all the saved sessions.
+    pub async fn list(&self) -> Vec<(GuildId, Session)> {
+        let Ok(mut entries) = fs::read_dir(&self.dir).await else {
+            return Vec::new();
+        };
+
+        let mut sessions = Vec::new();
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            let guild_id = path
+                .file_name()
+                .and_then(|name| name.to_str()?.strip_suffix(".json")?.parse().ok())
+                .map(GuildId::new);
+            let Some(guild_id) = guild_id else {
+                continue;
+            };
+            if let Some(session) = read_json(&path).await {
+                sessions.push((guild_id, session));
+            }
+        }
+        sessions
+    }
+}
+
+pub struct SessionStoreKey;
+
+impl TypeMapKey for SessionStoreKey {
+    type Value = SessionStore;
+}
+
+/// Saves the queue of every call each `interval`, forgetting the sessions of
+/// the calls that were left.
+pub async fn save_periodically(ctx: Context, interval: Duration) {
+    let Some(manager) = songbird::get(&ctx).await else {
+        return;
+    };
+    let (store, settings) = {
+        let data = ctx.data.read().await;
+        (
+            data.get::<SessionStoreKey>()
+                .expect("session store not found")
+                .clone(),
+            data.get::<GuildSettingsKey>()
+                .expect("guild settings not found")
+                .clone(),
+        )
+    };
+
+    let mut saved = HashSet::new();
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+
+        let mut active = HashSet::new();
+        for (guild_id, call) in manager.iter() {
+            let guild_id = GuildId::new(guild_id.0.get());
+            let (channel, queue) = {
+                let call = call.lock().await;
+                (call.current_channel(), call.queue().current_queue())
+            };
+            let Some(channel) = channel else {
+                continue;
+            };
+            active.insert(guild_id);
+
+            let guild_settings = settings.get(guild_id).await;
+            match Session::capture(ChannelId::new(channel.0.get()), &queue, &guild_settings).await {
+                Some(session) => store.save(guild_id, &session).await,
+                None => store.remove(guild_id).await,
+            }
+        }
+
+        // the bot left these calls on purpose, so there's nothing to resume
+        for guild_id in saved.difference(&active) {
+            store.remove(*guild_id).await;
+        }
+        saved = active;
+    }
+}
+
+/// Tells the guilds with a saved session that it can be resumed.
+pub async fn offer_resume(ctx: &Context) {
+    let store = {
+        let data = ctx.data.read().await;
+        data.get::<SessionStoreKey>()
+            .expect("session store not found")
+            .clone()
+    };
+
+    for (guild_id, session) in store.list().await {
+        let Some(channel_id) = session.last_channel else {
+            continue;
+        };
+        info!(
+            guild_id = guild_id.get(),
+            tracks = session.tracks.len(),
+            "offering to resume session"
+        );
+
+        let locale = session.locale.unwrap_or_default();
+        let message = t!(locale, "session.offer", count = session.tracks.len());
+        if let Err(cause) = channel_id.say(&ctx.http, message).await {
+            error!(%cause, "failed to send message");
+        }
+    }
+}
@@ -0,0 +1,47 @@
+//! JSON files in the bot's data directory, for what must survive a restart.
+
+use std::{io, path::Path};
+
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::fs;
+use tracing::error;
+
+/// Reads the value stored at `path`, or `None` if there's none or it can't
+/// be read.
+pub async fn read_json<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let contents = match fs::read(path).await {
+        Ok(contents) => contents,
+        Err(cause) if cause.kind() == io::ErrorKind::NotFound => return None,
+        Err(cause) => {
+            error!(%cause, ?path, "failed to read file");
+            return None;
+        }
+    };
+
+    serde_json::from_slice(&contents)
+        .map_err(|cause| error!(%cause, ?path, "failed to decode file"))
+        .ok()
+}
+
+/// Stores `value` at `path`, creating its directory if needed.
+///
+/// The file is written next to `path` and then moved over it, so that a
+/// crash never leaves it half written.
+pub async fn write_json<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).await?;
+    }
+
+    let contents = serde_json::to_vec(value)?;
+    let temporary = path.with_extension("json.tmp");
+    fs::write(&temporary, contents).await?;
+    fs::rename(&temporary, path).await
+}
+
+/// Deletes the file at `path`, if there's one.
+pub async fn remove_file(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path).await {
+        Err(cause) if cause.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
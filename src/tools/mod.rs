@@ -1 +1,3 @@
 pub mod piped;
+pub mod tags;
+pub mod ytdlp;
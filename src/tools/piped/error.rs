@@ -0,0 +1,124 @@
+use std::{error::Error, fmt, time::Duration};
+
+use reqwest::StatusCode;
+
+/// How much of an error response's body is kept
+const BODY_SNIPPET_SIZE: usize = 200;
+
+#[derive(Debug)]
+pub enum PipedError {
+    /// There are no instances to send the request to
+    NoInstances,
+    /// The URL of the request couldn't be built
+    InvalidUrl { url: String, reason: String },
+    /// The instance took too long to answer
+    Timeout { instance: String },
+    /// The connection to the instance failed
+    Connection {
+        instance: String,
+        source: reqwest::Error,
+    },
+    /// The instance answered with an error status
+    Status {
+        instance: String,
+        status: StatusCode,
+        /// The beginning of the response's body
+        body: String,
+    },
+    /// The instance is refusing requests for a while
+    RateLimited {
+        instance: String,
+        retry_after: Option<Duration>,
+    },
+    /// The response isn't in the expected format
+    Decode {
+        /// Where in the JSON the decoding failed, such as `items[3].duration`
+        path: String,
+        source: serde_json::Error,
+    },
+}
+
+impl PipedError {
+    /// Whether another instance, or the same one later, may answer the
+    /// request successfully.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            PipedError::Timeout { .. }
+            | PipedError::Connection { .. }
+            | PipedError::RateLimited { .. } => true,
+            PipedError::Status { status, .. } => status.is_server_error(),
+            // instances that are down for maintenance often answer with an
+            // HTML page instead of an error status
+            PipedError::Decode { .. } => true,
+            PipedError::NoInstances | PipedError::InvalidUrl { .. } => false,
+        }
+    }
+
+    pub(super) fn from_request(instance: &str, source: reqwest::Error) -> Self {
+        if source.is_timeout() {
+            PipedError::Timeout {
+                instance: instance.to_string(),
+            }
+        } else {
+            PipedError::Connection {
+                instance: instance.to_string(),
+                source,
+            }
+        }
+    }
+
+    pub(super) fn status(instance: &str, status: StatusCode, body: &str) -> Self {
+        let body = match body.char_indices().nth(BODY_SNIPPET_SIZE) {
+            Some((end, _)) => format!("{}…", &body[..end]),
+            None => body.to_string(),
+        };
+
+        PipedError::Status {
+            instance: instance.to_string(),
+            status,
+            body,
+        }
+    }
+}
+
+impl fmt::Display for PipedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PipedError::NoInstances => write!(f, "no piped instances are configured"),
+            PipedError::InvalidUrl { url, reason } => {
+                write!(f, "invalid piped URL `{url}`: {reason}")
+            }
+            PipedError::Timeout { instance } => write!(f, "{instance} timed out"),
+            PipedError::Connection { instance, .. } => {
+                write!(f, "failed to connect to {instance}")
+            }
+            PipedError::Status {
+                instance,
+                status,
+                body,
+            } => write!(f, "{instance} answered with {status}: {body:?}"),
+            PipedError::RateLimited {
+                instance,
+                retry_after: Some(retry_after),
+            } => write!(
+                f,
+                "{instance} is rate limiting, retry after {retry_after:?}"
+            ),
+            PipedError::RateLimited {
+                instance,
+                retry_after: None,
+            } => write!(f, "{instance} is rate limiting"),
+            PipedError::Decode { path, .. } => write!(f, "invalid response at `{path}`"),
+        }
+    }
+}
+
+impl Error for PipedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PipedError::Connection { source, .. } => Some(source),
+            PipedError::Decode { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
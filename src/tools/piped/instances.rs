@@ -0,0 +1,91 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tracing::warn;
+
+/// The Piped instances the clients can use, shared between them so that the
+/// failing ones are skipped by every request for a while.
+#[derive(Debug, Clone)]
+pub struct PipedInstances {
+    instances: Arc<Mutex<Vec<Instance>>>,
+    /// How long an instance is skipped after failing or being slow
+    cooldown: Duration,
+}
+
+#[derive(Debug)]
+struct Instance {
+    url: String,
+    unhealthy_until: Option<Instant>,
+}
+
+impl PipedInstances {
+    /// Creates the list from the base URLs of the instances' APIs, such as
+    /// `https://pipedapi.kavin.rocks`, in order of preference.
+    pub fn new<I, S>(urls: I, cooldown: Duration) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let instances = urls
+            .into_iter()
+            .map(|url| Instance {
+                url: url.into().trim_end_matches('/').to_string(),
+                unhealthy_until: None,
+            })
+            .collect();
+
+        Self {
+            instances: Arc::new(Mutex::new(instances)),
+            cooldown,
+        }
+    }
+
+    /// The URLs of the instances to try, in order. The healthy ones come
+    /// first, and the unhealthy ones are only kept as a last resort, the
+    /// closest to recovering first.
+    pub(super) fn candidates(&self) -> Vec<String> {
+        let now = Instant::now();
+        let instances = self.instances.lock().expect("instances lock poisoned");
+
+        let (healthy, mut unhealthy): (Vec<_>, Vec<_>) = instances
+            .iter()
+            .partition(|instance| instance.unhealthy_until.map_or(true, |until| until <= now));
+        unhealthy.sort_by_key(|instance| instance.unhealthy_until);
+
+        healthy
+            .into_iter()
+            .chain(unhealthy)
+            .map(|instance| instance.url.clone())
+            .collect()
+    }
+
+    /// Skips the instance for the cooldown period.
+    pub(super) fn mark_unhealthy(&self, url: &str) {
+        self.mark_unhealthy_for(url, self.cooldown);
+    }
+
+    /// Skips the instance for `duration`, or the cooldown period if it's
+    /// longer.
+    pub(super) fn mark_unhealthy_for(&self, url: &str, duration: Duration) {
+        let cooldown = duration.max(self.cooldown);
+        warn!(
+            instance = url,
+            ?cooldown,
+            "marking piped instance as unhealthy"
+        );
+        self.set_unhealthy_until(url, Some(Instant::now() + cooldown));
+    }
+
+    pub(super) fn mark_healthy(&self, url: &str) {
+        self.set_unhealthy_until(url, None);
+    }
+
+    fn set_unhealthy_until(&self, url: &str, until: Option<Instant>) {
+        let mut instances = self.instances.lock().expect("instances lock poisoned");
+        if let Some(instance) = instances.iter_mut().find(|instance| instance.url == url) {
+            instance.unhealthy_until = until;
+        }
+    }
+}
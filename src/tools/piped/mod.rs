@@ -1,77 +1,599 @@
-use std::borrow::Cow;
+use std::time::{Duration, Instant};
 
-use reqwest::{Client as Http, Url};
-use serde::Deserialize;
-use tracing::{debug, error};
+use reqwest::{header::RETRY_AFTER, Client as Http, Response, StatusCode, Url};
+use serde::{de::DeserializeOwned, Deserialize};
+use tracing::{debug, error, warn};
 
-const PIPED_URL: &str = "https://pipedapi.kavin.rocks";
+pub use self::{error::PipedError, instances::PipedInstances};
+
+mod error;
+mod instances;
+
+pub const PIPED_URL: &str = "https://pipedapi.kavin.rocks";
 const USER_AGENT: &str = concat!("piped-rust-sdk/0.0.4");
 
+/// How many times every instance is tried before giving up
+const MAX_ROUNDS: u32 = 3;
+/// The wait before the second round, doubled for each round after it
+const RETRY_BACKOFF: Duration = Duration::from_millis(250);
+/// How long an instance has to answer a request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Instances that take longer than this to answer are marked unhealthy, even
+/// though their answer is used
+const SLOW_RESPONSE: Duration = Duration::from_secs(5);
+
 pub struct PipedClient<'h> {
-    instance: Cow<'static, str>,
+    instances: &'h PipedInstances,
     http: &'h Http,
 }
 
 impl<'h> PipedClient<'h> {
-    pub fn new(http: &'h Http) -> Self {
-        Self {
-            http,
-            instance: Cow::Borrowed(PIPED_URL),
+    pub fn new(http: &'h Http, instances: &'h PipedInstances) -> Self {
+        Self { http, instances }
+    }
+
+    pub async fn search_songs(&self, input: &str) -> Result<SearchResults, PipedError> {
+        self.get("/search", &[("q", input), ("filter", "videos")])
+            .await
+    }
+
+    /// Fetches the results that come after a page of [`Self::search_songs`].
+    pub async fn search_songs_page(
+        &self,
+        input: &str,
+        nextpage: &str,
+    ) -> Result<SearchResults, PipedError> {
+        self.get(
+            "/nextpage/search",
+            &[("q", input), ("filter", "videos"), ("nextpage", nextpage)],
+        )
+        .await
+    }
+
+    /// Fetches up to `limit` videos of the playlist with the given id,
+    /// following the playlist pages as needed.
+    pub async fn playlist(&self, id: &str, limit: usize) -> Result<Playlist, PipedError> {
+        let mut playlist: Playlist = self.get(&format!("/playlists/{id}"), &[]).await?;
+
+        while playlist.related_streams.len() < limit {
+            let Some(nextpage) = playlist.nextpage.take() else {
+                break;
+            };
+
+            let page: PlaylistPage = self
+                .get(
+                    &format!("/nextpage/playlists/{id}"),
+                    &[("nextpage", &nextpage)],
+                )
+                .await?;
+            playlist.related_streams.extend(page.related_streams);
+            playlist.nextpage = page.nextpage;
         }
+
+        playlist.related_streams.truncate(limit);
+        Ok(playlist)
     }
 
-    #[allow(dead_code)]
-    pub fn set_instance<S: Into<String>>(mut self, url: S) -> Self {
-        self.instance = Cow::Owned(url.into());
-        self
+    /// Fetches the page of the video `id`, with the videos related to it.
+    pub async fn streams(&self, id: &str) -> Result<Streams, PipedError> {
+        self.get(&format!("/streams/{id}"), &[]).await
     }
 
-    pub async fn search_songs(&self, input: &str) -> Result<SearchResults, PipedError> {
-        let mut url = Url::parse(format!("{}/search", self.instance).as_str()).expect("bad URL");
-        url.query_pairs_mut().append_pair("q", input);
-        url.query_pairs_mut()
-            .append_pair("filter", "videos");
+    /// Fetches the SponsorBlock segments of the video `id` in the given
+    /// categories, such as `sponsor` or `music_offtopic`.
+    pub async fn sponsors(&self, id: &str, categories: &[&str]) -> Result<Sponsors, PipedError> {
+        let categories = serde_json::to_string(categories).unwrap_or_default();
+        self.get(&format!("/sponsors/{id}"), &[("category", &categories)])
+            .await
+    }
 
+    /// Requests `path` from the instances until one of them answers, waiting
+    /// longer between each round of tries.
+    async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, PipedError> {
+        let mut last_error = PipedError::NoInstances;
+        // the instances that answered with something else than the expected
+        // JSON, which isn't going to change in the next rounds
+        let mut undecodable = Vec::new();
+
+        for round in 0..MAX_ROUNDS {
+            if round > 0 {
+                tokio::time::sleep(RETRY_BACKOFF * 2u32.pow(round - 1)).await;
+            }
 
-        debug!("search url: {url:?}");
+            for instance in self.instances.candidates() {
+                if undecodable.contains(&instance) {
+                    continue;
+                }
 
-        let Ok(res) = self
+                let result = match self.get_from(&instance, path, query).await {
+                    Ok(body) => decode(&body).inspect_err(|_| {
+                        self.instances.mark_unhealthy(&instance);
+                        undecodable.push(instance.clone());
+                    }),
+                    Err(err) => Err(err),
+                };
+                match result {
+                    Ok(value) => return Ok(value),
+                    Err(err) if err.is_transient() => {
+                        warn!(instance, %err, "piped request failed");
+                        last_error = err;
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+        }
+
+        error!(path, %last_error, "every piped instance failed");
+        Err(last_error)
+    }
+
+    /// Requests `path` from a single instance, keeping track of its health.
+    async fn get_from(
+        &self,
+        instance: &str,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<String, PipedError> {
+        let url = format!("{instance}{path}");
+        let mut url = Url::parse(&url).map_err(|err| PipedError::InvalidUrl {
+            url,
+            reason: err.to_string(),
+        })?;
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+
+        debug!("requesting {url}");
+
+        let started_at = Instant::now();
+        let result = self
             .http
             .get(url)
             .header("user-agent", USER_AGENT)
+            .timeout(REQUEST_TIMEOUT)
             .send()
-            .await
-        else {
-            error!("request to {} failed: ", self.instance);
-            return Err(PipedError::Request);
+            .await;
+        let res = match result {
+            Ok(res) => res,
+            Err(err) => {
+                self.instances.mark_unhealthy(instance);
+                return Err(PipedError::from_request(instance, err));
+            }
         };
 
-        debug!("search status: {}", res.status());
-        let body = res.text().await.unwrap();
+        let status = res.status();
+        debug!(instance, %status, elapsed = ?started_at.elapsed(), "piped instance answered");
 
-        match serde_json::from_str(&body) {
-            Ok(res) => Ok(res),
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = retry_after(&res);
+            self.instances
+                .mark_unhealthy_for(instance, retry_after.unwrap_or_default());
+            return Err(PipedError::RateLimited {
+                instance: instance.to_string(),
+                retry_after,
+            });
+        }
+
+        let body = match res.text().await {
+            Ok(body) => body,
             Err(err) => {
-                error!("results serialization failed: {err:?}");
-                debug!("request body: {:?}", body);
-                Err(PipedError::Unknown)
+                self.instances.mark_unhealthy(instance);
+                return Err(PipedError::from_request(instance, err));
             }
+        };
+
+        if status.is_server_error() || started_at.elapsed() > SLOW_RESPONSE {
+            self.instances.mark_unhealthy(instance);
+        } else {
+            self.instances.mark_healthy(instance);
+        }
+
+        if !status.is_success() {
+            return Err(PipedError::status(instance, status, &body));
+        }
+
+        Ok(body)
+    }
+}
+
+/// Reads the `Retry-After` header, when it's given in seconds.
+fn retry_after(res: &Response) -> Option<Duration> {
+    res.headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+fn decode<T: DeserializeOwned>(body: &str) -> Result<T, PipedError> {
+    let deserializer = &mut serde_json::Deserializer::from_str(body);
+
+    serde_path_to_error::deserialize(deserializer).map_err(|err| {
+        debug!("response body: {:?}", body);
+        PipedError::Decode {
+            path: err.path().to_string(),
+            source: err.into_inner(),
         }
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchResults {
+    pub items: Vec<SearchItem>,
+    /// The token for [`PipedClient::search_songs_page`], if there are more
+    /// results
+    pub nextpage: Option<String>,
+}
+
+impl SearchResults {
+    /// The videos and live streams among the results.
+    pub fn into_streams(self) -> impl Iterator<Item = StreamItem> {
+        self.items.into_iter().filter_map(|item| match item {
+            SearchItem::Stream(stream) => Some(stream),
+            _ => None,
+        })
     }
 }
 
-pub enum PipedError {
-    Request,
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SearchItem {
+    Stream(StreamItem),
+    Channel(ChannelItem),
+    Playlist(PlaylistSearchItem),
+    /// Kinds of items this client doesn't know about
+    #[serde(other)]
     Unknown,
 }
 
 #[derive(Debug, Deserialize)]
-pub struct SearchResults {
-    pub items: Vec<SearchResult>,
+#[serde(rename_all = "camelCase")]
+pub struct StreamItem {
+    /// The path of the video, such as `/watch?v=dQw4w9WgXcQ`
+    pub url: String,
+    pub title: String,
+    pub thumbnail: Option<String>,
+    pub uploader_name: Option<String>,
+    /// When the video was uploaded, in milliseconds since the Unix epoch
+    #[serde(default)]
+    pub uploaded: Option<i64>,
+    /// In seconds, or `-1` for live streams
+    pub duration: i64,
+    /// `-1` when it's unknown
+    #[serde(default)]
+    pub views: i64,
+    #[serde(default)]
+    pub is_short: bool,
 }
+
+impl StreamItem {
+    /// The duration of the video, or `None` for live streams.
+    pub fn duration(&self) -> Option<Duration> {
+        u64::try_from(self.duration).ok().map(Duration::from_secs)
+    }
+
+    pub fn views(&self) -> Option<u64> {
+        u64::try_from(self.views).ok()
+    }
+}
+
+#[allow(dead_code)]
 #[derive(Debug, Deserialize)]
-pub struct SearchResult {
+#[serde(rename_all = "camelCase")]
+pub struct ChannelItem {
+    /// The path of the channel, such as `/channel/UCuAXFkgsw1L7xaCfnd5JJOw`
     pub url: String,
-    pub duration: u64,
+    pub name: String,
+    pub thumbnail: Option<String>,
+    #[serde(default)]
+    pub subscribers: i64,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistSearchItem {
+    /// The path of the playlist, such as `/playlist?list=PL...`
+    pub url: String,
+    pub name: String,
+    pub thumbnail: Option<String>,
+    pub uploader_name: Option<String>,
+    #[serde(default)]
+    pub videos: i64,
+}
+
+/// The page of a video
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Streams {
+    pub uploader: Option<String>,
+    #[serde(default)]
+    pub related_streams: Vec<SearchItem>,
+    #[serde(default)]
+    pub chapters: Vec<StreamChapter>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StreamChapter {
     pub title: String,
+    /// In seconds
+    pub start: u64,
+}
+
+impl Streams {
+    /// The videos and live streams related to the video.
+    pub fn into_related(self) -> impl Iterator<Item = StreamItem> {
+        self.related_streams
+            .into_iter()
+            .filter_map(|item| match item {
+                SearchItem::Stream(stream) => Some(stream),
+                _ => None,
+            })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Playlist {
+    pub name: String,
+    /// The total amount of videos in the playlist
+    #[serde(default)]
+    pub videos: usize,
+    pub related_streams: Vec<PlaylistItem>,
+    pub nextpage: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PlaylistPage {
+    related_streams: Vec<PlaylistItem>,
+    nextpage: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistItem {
+    pub url: String,
+    pub title: String,
+    pub uploader_name: Option<String>,
+    /// Negative for live streams
+    pub duration: i64,
+    pub thumbnail: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Sponsors {
+    #[serde(default)]
+    pub segments: Vec<SponsorSegment>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SponsorSegment {
+    pub category: String,
+    /// Where the segment starts and ends, in seconds
+    pub segment: [f64; 2],
+    /// `skip` for the segments that should be skipped, or `mute` and `poi`
+    /// for the ones that are only marked
+    #[serde(default)]
+    pub action_type: Option<String>,
+}
+
+impl SponsorSegment {
+    pub fn is_skippable(&self) -> bool {
+        self.action_type
+            .as_deref()
+            .map_or(true, |action| action == "skip")
+    }
+
+    pub fn start(&self) -> Duration {
+        Duration::try_from_secs_f64(self.segment[0]).unwrap_or_default()
+    }
+
+    pub fn end(&self) -> Duration {
+        Duration::try_from_secs_f64(self.segment[1]).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    const EMPTY_RESULTS: &str = r#"{"items": [], "nextpage": null}"#;
+
+    /// A local server that answers every request with the same response.
+    struct MockInstance {
+        url: String,
+        hits: Arc<AtomicUsize>,
+    }
+
+    impl MockInstance {
+        async fn start(status: u16, body: &'static str) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let hits = Arc::new(AtomicUsize::new(0));
+
+            let counter = hits.clone();
+            tokio::spawn(async move {
+                loop {
+                    let Ok((mut socket, _)) = listener.accept().await else {
+                        return;
+                    };
+                    counter.fetch_add(1, Ordering::SeqCst);
+
+                    // the requests have no body, so the headers are enough
+                    let mut request = Vec::new();
+                    let mut buf = [0; 1024];
+                    while !request.ends_with(b"\r\n\r\n") {
+                        match socket.read(&mut buf).await {
+                            Ok(0) | Err(_) => break,
+                            Ok(n) => request.extend_from_slice(&buf[..n]),
+                        }
+                    }
+
+                    let response = format!(
+                        "HTTP/1.1 {status} Mock\r\ncontent-type: application/json\r\n\
+                         content-length: {}\r\nconnection: close\r\n\r\n{body}",
+                        body.len()
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                    let _ = socket.shutdown().await;
+                }
+            });
+
+            Self { url, hits }
+        }
+
+        fn hits(&self) -> usize {
+            self.hits.load(Ordering::SeqCst)
+        }
+    }
+
+    /// The URL of a port nobody listens on, which refuses connections.
+    async fn closed_instance() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    }
+
+    async fn search(http: &Http, instances: &PipedInstances) -> Result<SearchResults, PipedError> {
+        PipedClient::new(http, instances).search_songs("song").await
+    }
+
+    #[tokio::test]
+    async fn fails_over_on_server_errors() {
+        let failing = MockInstance::start(502, "bad gateway").await;
+        let working = MockInstance::start(200, EMPTY_RESULTS).await;
+        let instances = PipedInstances::new([&failing.url, &working.url], Duration::from_secs(60));
+
+        assert!(search(&Http::new(), &instances).await.is_ok());
+        assert_eq!(failing.hits(), 1);
+        assert_eq!(working.hits(), 1);
+        assert_eq!(
+            instances.candidates(),
+            [working.url.as_str(), failing.url.as_str()]
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_over_on_connection_errors() {
+        let closed = closed_instance().await;
+        let working = MockInstance::start(200, EMPTY_RESULTS).await;
+        let instances = PipedInstances::new([&closed, &working.url], Duration::from_secs(60));
+
+        assert!(search(&Http::new(), &instances).await.is_ok());
+        assert_eq!(working.hits(), 1);
+        assert_eq!(
+            instances.candidates(),
+            [working.url.as_str(), closed.as_str()]
+        );
+    }
+
+    #[tokio::test]
+    async fn skips_unhealthy_instances_until_cooldown() {
+        let cooldown = Duration::from_millis(300);
+        let failing = MockInstance::start(500, "oops").await;
+        let working = MockInstance::start(200, EMPTY_RESULTS).await;
+        let instances = PipedInstances::new([&failing.url, &working.url], cooldown);
+        let http = Http::new();
+
+        assert!(search(&http, &instances).await.is_ok());
+        assert_eq!(failing.hits(), 1);
+
+        // the failed instance is left out during its cooldown
+        assert!(search(&http, &instances).await.is_ok());
+        assert_eq!(failing.hits(), 1);
+        assert_eq!(working.hits(), 2);
+
+        // and tried first again afterwards
+        tokio::time::sleep(cooldown).await;
+        assert!(search(&http, &instances).await.is_ok());
+        assert_eq!(failing.hits(), 2);
+        assert_eq!(working.hits(), 3);
+    }
+
+    #[tokio::test]
+    async fn retries_with_backoff_when_every_instance_fails() {
+        let first = MockInstance::start(503, "unavailable").await;
+        let second = MockInstance::start(500, "oops").await;
+        let instances = PipedInstances::new([&first.url, &second.url], Duration::from_secs(60));
+
+        let started_at = Instant::now();
+        let result = search(&Http::new(), &instances).await;
+
+        assert!(matches!(result, Err(PipedError::Status { .. })));
+        assert_eq!(first.hits(), MAX_ROUNDS as usize);
+        assert_eq!(second.hits(), MAX_ROUNDS as usize);
+        // the rounds after the first wait 250ms and then 500ms
+        assert!(started_at.elapsed() >= RETRY_BACKOFF * 3);
+    }
+
+    #[tokio::test]
+    async fn fails_over_on_undecodable_responses() {
+        let maintenance = MockInstance::start(200, "<html>Under maintenance</html>").await;
+        let working = MockInstance::start(200, EMPTY_RESULTS).await;
+        let instances =
+            PipedInstances::new([&maintenance.url, &working.url], Duration::from_secs(60));
+
+        assert!(search(&Http::new(), &instances).await.is_ok());
+        assert_eq!(maintenance.hits(), 1);
+        assert_eq!(working.hits(), 1);
+        assert_eq!(
+            instances.candidates(),
+            [working.url.as_str(), maintenance.url.as_str()]
+        );
+    }
+
+    #[tokio::test]
+    async fn tries_undecodable_instances_once() {
+        let maintenance = MockInstance::start(200, "<html>Under maintenance</html>").await;
+        let failing = MockInstance::start(503, "unavailable").await;
+        let instances =
+            PipedInstances::new([&maintenance.url, &failing.url], Duration::from_secs(60));
+
+        let result = search(&Http::new(), &instances).await;
+
+        assert!(matches!(result, Err(PipedError::Status { .. })));
+        assert_eq!(maintenance.hits(), 1);
+        assert_eq!(failing.hits(), MAX_ROUNDS as usize);
+    }
+
+    #[tokio::test]
+    async fn gives_up_on_client_errors() {
+        let missing = MockInstance::start(404, "not found").await;
+        let working = MockInstance::start(200, EMPTY_RESULTS).await;
+        let instances = PipedInstances::new([&missing.url, &working.url], Duration::from_secs(60));
+
+        let result = search(&Http::new(), &instances).await;
+
+        assert!(matches!(result, Err(PipedError::Status { .. })));
+        assert_eq!(missing.hits(), 1);
+        assert_eq!(working.hits(), 0);
+    }
+
+    #[tokio::test]
+    async fn fails_without_instances() {
+        let instances = PipedInstances::new(Vec::<String>::new(), Duration::from_secs(60));
+
+        let result = search(&Http::new(), &instances).await;
+
+        assert!(matches!(result, Err(PipedError::NoInstances)));
+    }
 }
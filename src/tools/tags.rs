@@ -0,0 +1,125 @@
+//! Reads what an audio file says about itself.
+
+use std::{io::Cursor, time::Duration};
+
+use symphonia::core::{
+    formats::FormatOptions,
+    io::{MediaSource, MediaSourceStream, ReadOnlySource},
+    meta::{MetadataOptions, MetadataRevision, StandardTagKey, StandardVisualKey},
+    probe::Hint,
+};
+
+/// The tags and the length of an audio file
+#[derive(Debug, Default, Clone)]
+pub struct FileTags {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub duration: Option<Duration>,
+    pub cover: Option<Cover>,
+}
+
+/// An image embedded in the file, such as the album's cover art
+#[derive(Debug, Clone)]
+pub struct Cover {
+    pub data: Vec<u8>,
+    /// The MIME type of the image, such as `image/jpeg`
+    pub media_type: String,
+}
+
+impl Cover {
+    /// The extension of a file with the image.
+    pub fn extension(&self) -> &str {
+        match self.media_type.as_str() {
+            "image/png" => "png",
+            "image/gif" => "gif",
+            "image/webp" => "webp",
+            _ => "jpg",
+        }
+    }
+}
+
+/// Probes the contents of an audio file, returning `None` if its format
+/// isn't supported.
+///
+/// The `extension` of the file's name helps guessing the format. When the
+/// `contents` aren't `complete`, only the start of the file, the length is
+/// only known if the headers tell it.
+pub fn read_tags(contents: Vec<u8>, extension: Option<&str>, complete: bool) -> Option<FileTags> {
+    let mut hint = Hint::new();
+    if let Some(extension) = extension {
+        hint.with_extension(extension);
+    }
+
+    // the readers that look for the length at the end of the file would
+    // find the end of the partial contents instead
+    let source: Box<dyn MediaSource> = if complete {
+        Box::new(Cursor::new(contents))
+    } else {
+        Box::new(ReadOnlySource::new(Cursor::new(contents)))
+    };
+    let source = MediaSourceStream::new(source, Default::default());
+    let mut probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            source,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .ok()?;
+
+    let mut tags = FileTags::default();
+
+    // the container's own tags, such as Vorbis comments, take precedence
+    // over the ones found before it, such as ID3
+    if let Some(revision) = probed.metadata.get().as_ref().and_then(|m| m.current()) {
+        apply_revision(&mut tags, revision);
+    }
+    if let Some(revision) = probed.format.metadata().current() {
+        apply_revision(&mut tags, revision);
+    }
+
+    tags.duration = probed.format.default_track().and_then(|track| {
+        let params = &track.codec_params;
+        let frames = params.n_frames?;
+        let time = match params.time_base {
+            Some(time_base) => time_base.calc_time(frames),
+            None => {
+                let rate = u64::from(params.sample_rate?);
+                return Duration::try_from_secs_f64(frames as f64 / rate as f64).ok();
+            }
+        };
+        Some(Duration::from_secs(time.seconds) + Duration::from_secs_f64(time.frac))
+    });
+
+    Some(tags)
+}
+
+fn apply_revision(tags: &mut FileTags, revision: &MetadataRevision) {
+    for tag in revision.tags() {
+        let value = tag.value.to_string().trim().to_string();
+        if value.is_empty() {
+            continue;
+        }
+
+        match tag.std_key {
+            Some(StandardTagKey::TrackTitle) => tags.title = Some(value),
+            Some(StandardTagKey::Artist) => tags.artist = Some(value),
+            // only used if the track has no artist of its own
+            Some(StandardTagKey::AlbumArtist) if tags.artist.is_none() => tags.artist = Some(value),
+            _ => {}
+        }
+    }
+
+    // the front cover is preferred, but any image is better than none
+    let visual = revision
+        .visuals()
+        .iter()
+        .find(|visual| visual.usage == Some(StandardVisualKey::FrontCover))
+        .or_else(|| revision.visuals().first());
+    if let Some(visual) = visual {
+        tags.cover = Some(Cover {
+            data: visual.data.to_vec(),
+            media_type: visual.media_type.clone(),
+        });
+    }
+}
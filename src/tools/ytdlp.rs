@@ -0,0 +1,132 @@
+//! Asks yt-dlp about a page, keeping the fields that songbird's metadata
+//! leaves out, such as the chapters.
+
+use std::{fmt, io, time::Duration};
+
+use serde::Deserialize;
+use tokio::process::Command;
+
+const YTDLP: &str = "yt-dlp";
+/// How long yt-dlp may take to extract a page before it's given up on
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub enum YtDlpError {
+    /// yt-dlp couldn't be run at all
+    Spawn(io::Error),
+    /// yt-dlp ran, but couldn't extract the page
+    Failed(String),
+    /// yt-dlp took longer than [`TIMEOUT`], so it was killed
+    Timeout,
+    Decode(serde_json::Error),
+}
+
+impl fmt::Display for YtDlpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            YtDlpError::Spawn(cause) => write!(f, "failed to run {YTDLP}: {cause}"),
+            YtDlpError::Failed(stderr) => write!(f, "{YTDLP} failed: {stderr}"),
+            YtDlpError::Timeout => write!(f, "{YTDLP} timed out"),
+            YtDlpError::Decode(cause) => write!(f, "invalid {YTDLP} output: {cause}"),
+        }
+    }
+}
+
+impl std::error::Error for YtDlpError {}
+
+/// The info JSON of a page
+#[derive(Debug, Deserialize)]
+pub struct Info {
+    pub title: Option<String>,
+    /// The page of the video, which differs from the URL given to yt-dlp
+    /// for searches such as `scsearch1:artist song`
+    pub webpage_url: Option<String>,
+    /// In seconds
+    pub duration: Option<f64>,
+    pub thumbnail: Option<String>,
+    pub channel: Option<String>,
+    pub artist: Option<String>,
+    pub uploader: Option<String>,
+    /// `None` when the video or the site doesn't have chapters
+    pub chapters: Option<Vec<InfoChapter>>,
+}
+
+impl Info {
+    pub fn duration(&self) -> Option<Duration> {
+        self.duration
+            .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InfoChapter {
+    pub title: String,
+    /// In seconds
+    pub start_time: f64,
+}
+
+/// Runs yt-dlp for the info of the single song at `url`, or of the first
+/// song when `url` is a playlist.
+pub async fn info(url: &str) -> Result<Info, YtDlpError> {
+    let output = Command::new(YTDLP)
+        .args(["-j", "--no-playlist", "--playlist-items", "1", url])
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(TIMEOUT, output)
+        .await
+        .map_err(|_| YtDlpError::Timeout)?
+        .map_err(YtDlpError::Spawn)?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(YtDlpError::Failed(stderr.trim().to_string()));
+    }
+
+    parse_info(&output.stdout)
+}
+
+/// Parses the first info JSON printed by yt-dlp, which prints one per line
+/// for the entries of playlists.
+fn parse_info(stdout: &[u8]) -> Result<Info, YtDlpError> {
+    let first = stdout
+        .split(|&byte| byte == b'\n')
+        .find(|line| !line.trim_ascii().is_empty())
+        .unwrap_or_default();
+    serde_json::from_slice(first).map_err(YtDlpError::Decode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_single_info() {
+        let info = parse_info(br#"{"title": "Song", "duration": 83.5}"#).unwrap();
+
+        assert_eq!(info.title.as_deref(), Some("Song"));
+        assert_eq!(info.duration(), Some(Duration::from_secs_f64(83.5)));
+        assert!(info.chapters.is_none());
+    }
+
+    #[test]
+    fn parses_first_entry_of_playlist() {
+        let stdout = concat!(
+            r#"{"title": "First", "webpage_url": "https://example.bandcamp.com/track/first"}"#,
+            "\n",
+            r#"{"title": "Second", "webpage_url": "https://example.bandcamp.com/track/second"}"#,
+            "\n",
+        );
+        let info = parse_info(stdout.as_bytes()).unwrap();
+
+        assert_eq!(info.title.as_deref(), Some("First"));
+        assert_eq!(
+            info.webpage_url.as_deref(),
+            Some("https://example.bandcamp.com/track/first")
+        );
+    }
+
+    #[test]
+    fn rejects_empty_output() {
+        assert!(matches!(parse_info(b"\n"), Err(YtDlpError::Decode(_))));
+    }
+}